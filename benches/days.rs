@@ -0,0 +1,8 @@
+/// Benchmarks every scaffolded day on its real input, see `advent_of_code::template::harness`.
+mod solutions {
+    include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+}
+
+fn main() {
+    advent_of_code::template::harness::main(solutions::SOLUTIONS);
+}
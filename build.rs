@@ -0,0 +1,67 @@
+/// Generates the solution registry that `cargo all` uses to run solutions in-process.
+/// Every scaffolded day in `src/bin` is included as a module of the main binary.
+use std::{
+    env,
+    fmt::Write,
+    fs,
+    path::{Path, PathBuf},
+};
+
+fn main() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let out_dir = env::var("OUT_DIR").unwrap();
+    let bin_dir = Path::new(&manifest_dir).join("src").join("bin");
+
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-changed={}", bin_dir.display());
+
+    let days = scaffolded_days(&bin_dir);
+    let mut registry = String::new();
+
+    for (day, path) in &days {
+        writeln!(
+            registry,
+            "#[allow(dead_code, unused_imports)]\nmod day_{day:02} {{\n    include!({path:?});\n}}\n"
+        )
+        .unwrap();
+    }
+
+    writeln!(
+        registry,
+        "pub static SOLUTIONS: &[advent_of_code::template::registry::Solution] = &["
+    )
+    .unwrap();
+
+    for (day, _) in &days {
+        writeln!(registry, "    day_{day:02}::SOLUTION,").unwrap();
+    }
+
+    writeln!(registry, "];").unwrap();
+
+    fs::write(Path::new(&out_dir).join("solutions.rs"), registry).unwrap();
+}
+
+/// Collects every `src/bin/DD.rs` file whose name is a valid day of advent.
+fn scaffolded_days(bin_dir: &Path) -> Vec<(u8, PathBuf)> {
+    let Ok(entries) = fs::read_dir(bin_dir) else {
+        return vec![];
+    };
+
+    let mut days: Vec<(u8, PathBuf)> = entries
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+
+            if path.extension()? != "rs" {
+                return None;
+            }
+
+            let stem = path.file_stem()?.to_str()?;
+            let day = stem.parse::<u8>().ok()?;
+
+            (stem.len() == 2 && (1..=25).contains(&day)).then_some((day, path))
+        })
+        .collect();
+
+    days.sort();
+    days
+}
@@ -1,15 +1,23 @@
+use advent_of_code::template::parse_error::ParseError;
 use regex::Regex;
 use std::collections::HashMap;
 
 advent_of_code::solution!(1);
 
-pub fn part_one(input: &str) -> Option<u32> {
+pub fn part_one(input: &str) -> Result<u32, ParseError> {
     let mut total = 0;
     for line in input.lines() {
+        let missing_digit = || ParseError::new(line, "a line containing a digit");
         // find the first digit
-        let first_digit = line.chars().find(|c| c.is_digit(10)).unwrap();
+        let first_digit = line
+            .chars()
+            .find(|c| c.is_ascii_digit())
+            .ok_or_else(missing_digit)?;
         // find the last digit
-        let last_digit = line.chars().rfind(|c| c.is_digit(10)).unwrap();
+        let last_digit = line
+            .chars()
+            .rfind(|c| c.is_ascii_digit())
+            .ok_or_else(missing_digit)?;
         // concat the digits
         let digits = format!("{}{}", first_digit, last_digit)
             .parse::<u32>()
@@ -18,10 +26,10 @@ pub fn part_one(input: &str) -> Option<u32> {
         total += digits;
     }
 
-    Some(total)
+    Ok(total)
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
+pub fn part_two(input: &str) -> Result<u32, ParseError> {
     let digit_map: HashMap<_, _> = [
         ("one", "1"),
         ("two", "2"),
@@ -43,14 +51,19 @@ pub fn part_two(input: &str) -> Option<u32> {
 
     let mut total = 0;
     for line in input.lines() {
+        let missing_digit =
+            || ParseError::new(line, "a line containing a digit or a spelled out digit");
         // find first digit from pattern
-        let first_digit_str = pattern.find(line).unwrap().as_str();
+        let first_digit_str = pattern.find(line).ok_or_else(missing_digit)?.as_str();
         let first_digit = *digit_map.get(first_digit_str).unwrap_or(&first_digit_str);
 
         // find last digit from pattern
         // we use reversed string and pattern since there are overlapping matches
         let line_reversed = line.chars().rev().collect::<String>();
-        let last_digit_str_rev = pattern_reversed.find(&line_reversed).unwrap().as_str();
+        let last_digit_str_rev = pattern_reversed
+            .find(&line_reversed)
+            .ok_or_else(missing_digit)?
+            .as_str();
         let last_digit_str = last_digit_str_rev.chars().rev().collect::<String>();
         let last_digit = *digit_map
             .get(last_digit_str.as_str())
@@ -64,7 +77,7 @@ pub fn part_two(input: &str) -> Option<u32> {
         // add to total
         total += digits;
     }
-    Some(total)
+    Ok(total)
 }
 
 #[cfg(test)]
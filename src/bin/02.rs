@@ -1,13 +1,24 @@
 use std::collections::HashMap;
 
-use itertools::Itertools;
+use advent_of_code::template::parse_error::{parse_number, split_once, ParseError};
 
 advent_of_code::solution!(2);
-fn get_game_power(line: &str) -> usize {
+/// Split a draw like `3 blue` into its value and color.
+fn parse_draw(val_color: &str) -> Result<(usize, &str), ParseError> {
+    let (val, color) = split_once(val_color, " ")?;
+
+    if !["red", "green", "blue"].contains(&color) {
+        return Err(ParseError::new(color, "red, green or blue"));
+    }
+
+    Ok((parse_number(val)?, color))
+}
+
+fn get_game_power(line: &str) -> Result<usize, ParseError> {
     let mut game_max_colors_hashmap: HashMap<_, _> =
         HashMap::from_iter([("red", 0), ("green", 0), ("blue", 0)]);
 
-    let (_, outcome) = line.split(": ").collect_tuple().unwrap();
+    let (_, outcome) = split_once(line, ": ")?;
 
     // split line by ";"
     let draw_split = outcome.split("; ");
@@ -15,8 +26,7 @@ fn get_game_power(line: &str) -> usize {
         // split by ","
         for val_color in draw.split(", ") {
             // split by " "
-            let (val, color) = val_color.split(' ').collect_tuple().unwrap();
-            let val = val.parse::<usize>().unwrap();
+            let (val, color) = parse_draw(val_color)?;
 
             // update color max value
             game_max_colors_hashmap
@@ -25,10 +35,10 @@ fn get_game_power(line: &str) -> usize {
     }
 
     // calculate game power
-    game_max_colors_hashmap.values().product()
+    Ok(game_max_colors_hashmap.values().product())
 }
 
-fn is_game_possible(line: &str) -> Option<usize> {
+fn is_game_possible(line: &str) -> Result<Option<usize>, ParseError> {
     let max_colors_hashmap: HashMap<&str, usize> =
         HashMap::from_iter([("red", 12), ("green", 13), ("blue", 14)]);
     let max_total: usize = max_colors_hashmap.values().sum();
@@ -36,7 +46,7 @@ fn is_game_possible(line: &str) -> Option<usize> {
     let mut game_max_colors_hashmap: HashMap<_, _> =
         HashMap::from_iter([("red", 0), ("green", 0), ("blue", 0)]);
 
-    let (game_name, outcome) = line.split(": ").collect_tuple().unwrap();
+    let (game_name, outcome) = split_once(line, ": ")?;
 
     // split line by ";"
     let draw_split = outcome.split("; ");
@@ -44,12 +54,11 @@ fn is_game_possible(line: &str) -> Option<usize> {
         // split by ","
         for val_color in draw.split(", ") {
             // split by " "
-            let (val, color) = val_color.split(' ').collect_tuple().unwrap();
-            let val = val.parse::<usize>().unwrap();
+            let (val, color) = parse_draw(val_color)?;
 
             // check if color value is greater than max possible value
             if val > max_colors_hashmap[color] {
-                return None;
+                return Ok(None);
             }
 
             // update color max value
@@ -59,43 +68,30 @@ fn is_game_possible(line: &str) -> Option<usize> {
 
         // check if game is possible by summing max values
         if game_max_colors_hashmap.values().sum::<usize>() > max_total {
-            return None;
+            return Ok(None);
         }
     }
 
     // extract game name number
-    Some(
-        game_name
-            .split(' ')
-            .last()
-            .unwrap()
-            .parse::<usize>()
-            .unwrap(),
-    )
+    let (_, game_number) = split_once(game_name, " ")?;
+    Ok(Some(parse_number(game_number)?))
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
+pub fn part_one(input: &str) -> Result<u32, ParseError> {
     // split input lines
     let games = input.lines();
 
     // sum all possible games
-    Some(
-        games
-            .filter_map(|line| is_game_possible(line))
-            .sum::<usize>() as u32,
-    )
+    let possible_games = games.map(is_game_possible).collect::<Result<Vec<_>, _>>()?;
+    Ok(possible_games.into_iter().flatten().sum::<usize>() as u32)
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
+pub fn part_two(input: &str) -> Result<u32, ParseError> {
     // split input lines
     let games = input.lines();
 
     // sum all possible games
-    Some(
-        games
-            .map(|line| get_game_power(line))
-            .sum::<usize>() as u32,
-    )
+    Ok(games.map(get_game_power).sum::<Result<usize, _>>()? as u32)
 }
 
 #[cfg(test)]
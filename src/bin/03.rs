@@ -1,7 +1,8 @@
+use advent_of_code::template::parse_error::ParseError;
 use regex::Regex;
 use std::str::FromStr;
 
-advent_of_code::solution!(3);
+advent_of_code::solution!(3, parse?);
 
 #[derive(Clone, Copy, Debug)]
 struct PartNumber {
@@ -13,7 +14,7 @@ struct PartNumber {
 
 // struct containing the data for the problem
 // 2d char array
-struct DataMatrix {
+pub struct DataMatrix {
     data: Vec<String>,
     rows: u32,
     cols: u32,
@@ -32,8 +33,8 @@ impl DataMatrix {
         if adj_parts.len() != 2 {
             return 0;
         }
-        let ratio = adj_parts[0].value * adj_parts[1].value;
-        ratio
+
+        adj_parts[0].value * adj_parts[1].value
     }
 
     fn get_adjacent_part_numbers(
@@ -83,7 +84,7 @@ impl DataMatrix {
         for row in row_above..=row_below {
             for col in col_left..=col_right {
                 let cell_val = self.get(row, col).unwrap();
-                if !cell_val.is_digit(10) && cell_val != '.' {
+                if !cell_val.is_ascii_digit() && cell_val != '.' {
                     return true;
                 }
             }
@@ -122,26 +123,42 @@ impl DataMatrix {
 }
 
 impl FromStr for DataMatrix {
-    type Err = anyhow::Error;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let first_line = s
+            .lines()
+            .next()
+            .ok_or_else(|| ParseError::missing(s, "a grid of characters"))?;
+        let cols = first_line.len();
+
+        // every row needs the same width, otherwise lookups of neighbouring cells would go out of bounds.
+        if let Some(line) = s.lines().find(|line| line.len() != cols) {
+            return Err(ParseError::new(line, format!("a row of {cols} characters")));
+        }
+
         let data = s
             .lines()
             .map(|line| line.to_string())
             .collect::<Vec<String>>();
         let rows = data.len() as u32;
-        let cols = data[0].len() as u32;
-        Ok(DataMatrix { data, rows, cols })
+        Ok(DataMatrix {
+            data,
+            rows,
+            cols: cols as u32,
+        })
     }
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let data_matrix = DataMatrix::from_str(input).unwrap();
+pub fn parse(input: &str) -> Result<DataMatrix, ParseError> {
+    DataMatrix::from_str(input)
+}
+
+pub fn part_one(data_matrix: &DataMatrix) -> Option<u32> {
     Some(data_matrix.get_sum_of_part_numbers_adjacent_to_symbol())
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let data_matrix = DataMatrix::from_str(input).unwrap();
+pub fn part_two(data_matrix: &DataMatrix) -> Option<u32> {
     Some(data_matrix.get_sum_of_gear_ratios())
 }
 
@@ -151,13 +168,15 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result =
+            part_one(&parse(&advent_of_code::template::read_file("examples", DAY)).unwrap());
         assert_eq!(result, Some(4361));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result =
+            part_two(&parse(&advent_of_code::template::read_file("examples", DAY)).unwrap());
         assert_eq!(result, Some(467835));
     }
 }
@@ -1,8 +1,8 @@
 use std::{collections::HashSet, str::FromStr};
 
-use regex::Regex;
+use advent_of_code::template::parse_error::{parse_number, split_once, ParseError};
 
-advent_of_code::solution!(4);
+advent_of_code::solution!(4, parse?);
 
 struct Card {
     winning_numbers: HashSet<u32>,
@@ -33,7 +33,31 @@ impl Card {
     }
 }
 
-struct Pile {
+impl FromStr for Card {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, numbers) = split_once(s, ":")?;
+        if !name.starts_with("Card ") {
+            return Err(ParseError::new(name, "a card name like \"Card 1\""));
+        }
+
+        let (winning_numbers, owned_numbers) = split_once(numbers, "|")?;
+        let parse_numbers = |numbers: &str| {
+            numbers
+                .split_whitespace()
+                .map(parse_number)
+                .collect::<Result<Vec<u32>, _>>()
+        };
+
+        Ok(Card::new(
+            parse_numbers(winning_numbers)?,
+            parse_numbers(owned_numbers)?,
+        ))
+    }
+}
+
+pub struct Pile {
     cards: Vec<Card>,
 }
 
@@ -59,35 +83,26 @@ impl Pile {
 }
 
 impl FromStr for Pile {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let re = Regex::new(r"Card +\d+\:([0-9 ]+)\|([0-9 ]+)").unwrap();
-        let mut cards: Vec<Card> = Vec::new();
-        let lines = s.lines();
-        for line in lines {
-            let captures = re.captures(line).unwrap();
-            let winning_numbers: Vec<u32> = captures[1]
-                .split_whitespace()
-                .map(|x| x.parse::<u32>().unwrap())
-                .collect();
-            let owned_numbers: Vec<u32> = captures[2]
-                .split_whitespace()
-                .map(|x| x.parse::<u32>().unwrap())
-                .collect();
-            cards.push(Card::new(winning_numbers, owned_numbers));
-        }
+        let cards = s
+            .lines()
+            .map(Card::from_str)
+            .collect::<Result<Vec<Card>, _>>()?;
         Ok(Self { cards })
     }
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let pile = Pile::from_str(input).unwrap();
+pub fn parse(input: &str) -> Result<Pile, ParseError> {
+    Pile::from_str(input)
+}
+
+pub fn part_one(pile: &Pile) -> Option<u32> {
     Some(pile.get_score())
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let pile = Pile::from_str(input).unwrap();
+pub fn part_two(pile: &Pile) -> Option<u32> {
     Some(pile.get_scores_new_rules())
 }
 
@@ -97,13 +112,15 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result =
+            part_one(&parse(&advent_of_code::template::read_file("examples", DAY)).unwrap());
         assert_eq!(result, Some(13));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result =
+            part_two(&parse(&advent_of_code::template::read_file("examples", DAY)).unwrap());
         assert_eq!(result, Some(30));
     }
 }
@@ -3,10 +3,10 @@ use std::{
     str::FromStr,
 };
 
+use advent_of_code::template::parse_error::{parse_number, split_once, ParseError};
 use itertools::Itertools;
-use regex::Regex;
 
-advent_of_code::solution!(5);
+advent_of_code::solution!(5, parse?);
 
 #[derive(Hash, Eq, PartialEq, Debug, Clone, Copy)]
 enum Category {
@@ -21,7 +21,7 @@ enum Category {
 }
 
 impl FromStr for Category {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
@@ -33,7 +33,7 @@ impl FromStr for Category {
             "temperature" => Ok(Category::Temperature),
             "humidity" => Ok(Category::Humidity),
             "location" => Ok(Category::Location),
-            _ => Err(()),
+            _ => Err(ParseError::new(s, "a category like \"seed\" or \"soil\"")),
         }
     }
 }
@@ -67,27 +67,35 @@ impl CategoryMapping {
 }
 
 impl FromStr for CategoryMapping {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut lines = s.lines();
-        let cap = Regex::new(r"([a-z]+)-to-([a-z]+) map:")
-            .unwrap()
-            .captures(lines.next().unwrap())
-            .unwrap();
-        let (source, target) = (cap[1].parse().unwrap(), cap[2].parse().unwrap());
+        let header = lines.next().unwrap_or_default();
+        let name = header
+            .strip_suffix(" map:")
+            .ok_or_else(|| ParseError::new(header, "a header like \"seed-to-soil map:\""))?;
+        let (source, target) = split_once(name, "-to-")?;
+        let (source, target) = (source.parse()?, target.parse()?);
         let mut entries = BTreeMap::new();
         for line in lines {
-            let (dest, src, len) = line.split(" ").collect_tuple().unwrap();
+            let (dest, src, len) = line
+                .split(' ')
+                .collect_tuple()
+                .ok_or_else(|| ParseError::new(line, "three numbers separated by spaces"))?;
+            let source_range_start = parse_number(src)?;
             entries.insert(
-                src.parse().unwrap(),
+                source_range_start,
                 CategoryMapEntry {
-                    source_range_start: src.parse().unwrap(),
-                    destination_range_start: dest.parse().unwrap(),
-                    range_length: len.parse().unwrap(),
+                    source_range_start,
+                    destination_range_start: parse_number(dest)?,
+                    range_length: parse_number(len)?,
                 },
             );
         }
+        if entries.is_empty() {
+            return Err(ParseError::missing(header, "at least one mapping"));
+        }
         Ok(CategoryMapping {
             source,
             target,
@@ -96,7 +104,7 @@ impl FromStr for CategoryMapping {
     }
 }
 
-struct Almanac {
+pub struct Almanac {
     seeds: Vec<u64>,
     mappings: HashMap<(Category, Category), CategoryMapping>,
     category_map: HashMap<Category, Category>,
@@ -131,8 +139,7 @@ impl Almanac {
         let min_all_seeds = self
             .seeds
             .chunks(2)
-            .map(|c| c[0]..(c[0] + c[1]))
-            .flatten()
+            .flat_map(|c| c[0]..(c[0] + c[1]))
             .map(|seed| self.get_seed_location(seed))
             .min()
             .unwrap();
@@ -140,21 +147,23 @@ impl Almanac {
     }
 }
 impl FromStr for Almanac {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut sections = s.split("\n\n");
-        let re = Regex::new(r"\d+").unwrap();
-        let seeds = re
-            .find_iter(sections.next().unwrap())
-            .map(|m| m.as_str().parse().unwrap())
-            .collect::<Vec<u64>>();
+        let seeds_section = sections.next().unwrap_or_default();
+        let seeds = seeds_section
+            .strip_prefix("seeds:")
+            .ok_or_else(|| ParseError::new(seeds_section, "a list like \"seeds: 79 14\""))?
+            .split_whitespace()
+            .map(parse_number)
+            .collect::<Result<Vec<u64>, _>>()?;
         let mut mappings = HashMap::new();
         let mut category_map = HashMap::new();
-        for section in sections {
-            let mapping = section.parse::<CategoryMapping>().unwrap();
-            let source = mapping.source.clone();
-            let target = mapping.target.clone();
+        for section in sections.filter(|section| !section.trim().is_empty()) {
+            let mapping = section.parse::<CategoryMapping>()?;
+            let source = mapping.source;
+            let target = mapping.target;
             mappings.insert((mapping.source, mapping.target), mapping);
             category_map.insert(source, target);
         }
@@ -166,13 +175,15 @@ impl FromStr for Almanac {
     }
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let almanac = input.parse::<Almanac>().unwrap();
+pub fn parse(input: &str) -> Result<Almanac, ParseError> {
+    input.parse::<Almanac>()
+}
+
+pub fn part_one(almanac: &Almanac) -> Option<u64> {
     Some(almanac.get_lowest_seed_location())
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let almanac = input.parse::<Almanac>().unwrap();
+pub fn part_two(almanac: &Almanac) -> Option<u64> {
     Some(almanac.get_lowest_seed_location_ranges())
 }
 
@@ -182,13 +193,15 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result =
+            part_one(&parse(&advent_of_code::template::read_file("examples", DAY)).unwrap());
         assert_eq!(result, Some(35));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result =
+            part_two(&parse(&advent_of_code::template::read_file("examples", DAY)).unwrap());
         assert_eq!(result, Some(46));
     }
 }
@@ -1,10 +1,11 @@
 use std::str::FromStr;
 
+use advent_of_code::template::parse_error::{parse_number, ParseError};
 use itertools::Itertools;
 
-advent_of_code::solution!(6);
+advent_of_code::solution!(6, parse?);
 
-struct Race {
+pub struct Race {
     duration: u64,
     record_distance: u64,
 }
@@ -32,33 +33,40 @@ impl Race {
     }
 }
 
+/// The line of the sheet that starts with `prefix`, e.g. `"Time:"`.
+fn sheet_line<'a>(
+    line: Option<&'a str>,
+    after: &'a str,
+    prefix: &str,
+) -> Result<&'a str, ParseError> {
+    let line =
+        line.ok_or_else(|| ParseError::missing(after, format!("a line starting with {prefix:?}")))?;
+    line.strip_prefix(prefix)
+        .ok_or_else(|| ParseError::new(line, format!("a line starting with {prefix:?}")))
+}
+
 impl FromStr for Race {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let lines = s.lines();
-
-        let (duration, record) = lines
-            .map(|s| {
-                s.split_once(":")
-                    .unwrap()
-                    .1
-                    .split_whitespace()
-                    .collect::<String>()
-                    .parse::<u64>()
-                    .ok()
-            })
-            .collect_tuple()
-            .unwrap();
+        let mut lines = s.lines();
+        let mut read_number = |prefix: &str| -> Result<u64, ParseError> {
+            let numbers = sheet_line(lines.next(), s.trim_end(), prefix)?;
+            numbers
+                .split_whitespace()
+                .collect::<String>()
+                .parse()
+                .map_err(|_| ParseError::new(numbers.trim(), "numbers"))
+        };
 
         Ok(Self {
-            duration: duration.unwrap(),
-            record_distance: record.unwrap(),
+            duration: read_number("Time:")?,
+            record_distance: read_number("Distance:")?,
         })
     }
 }
 
-struct Races {
+pub struct Races {
     races: Vec<Race>,
 }
 
@@ -72,25 +80,22 @@ impl Races {
 }
 
 impl FromStr for Races {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let mut lines = s.lines();
-        let durations = lines
-            .next()
-            .and_then(|s| s.strip_prefix("Time: "))
-            .expect("Expected 'Time: ' line prefix")
-            .split_whitespace()
-            .map(|s| s.parse::<u64>().unwrap());
-
-        let record_distance = lines
-            .next()
-            .and_then(|s| s.strip_prefix("Distance: "))
-            .expect("Expected 'Distance: ' line prefix")
-            .split_whitespace()
-            .map(|s| s.parse::<u64>().unwrap());
+        let mut read_numbers = |prefix: &str| -> Result<Vec<u64>, ParseError> {
+            sheet_line(lines.next(), s.trim_end(), prefix)?
+                .split_whitespace()
+                .map(parse_number)
+                .collect()
+        };
+
+        let durations = read_numbers("Time:")?;
+        let record_distance = read_numbers("Distance:")?;
 
         let races = durations
+            .into_iter()
             .zip(record_distance)
             .map(|(d, r)| Race {
                 duration: d,
@@ -102,13 +107,16 @@ impl FromStr for Races {
     }
 }
 
-pub fn part_one(input: &str) -> Option<u64> {
-    let races = input.parse::<Races>().unwrap();
+/// Part one reads the sheet as separate races, part two as a single race with the spaces removed.
+pub fn parse(input: &str) -> Result<(Races, Race), ParseError> {
+    Ok((input.parse()?, input.parse()?))
+}
+
+pub fn part_one((races, _): &(Races, Race)) -> Option<u64> {
     Some(races.get_num_option_prod())
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let race = input.parse::<Race>().unwrap();
+pub fn part_two((_, race): &(Races, Race)) -> Option<u64> {
     Some(race.get_num_options_to_beat_record())
 }
 
@@ -118,13 +126,15 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result =
+            part_one(&parse(&advent_of_code::template::read_file("examples", DAY)).unwrap());
         assert_eq!(result, Some(288));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result =
+            part_two(&parse(&advent_of_code::template::read_file("examples", DAY)).unwrap());
         assert_eq!(result, Some(71503));
     }
 }
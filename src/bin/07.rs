@@ -1,37 +1,43 @@
 use std::str::FromStr;
 
+use advent_of_code::template::parse_error::{parse_number, split_once, ParseError};
 use itertools::Itertools;
 
-advent_of_code::solution!(7);
+advent_of_code::solution!(7, parse?);
 
 #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
 enum Card {
     Joker,
     Number(u32),
     Ten,
-    // Jack,
+    Jack,
     Queen,
     King,
     Ace,
 }
 
 impl FromStr for Card {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
             "T" => Ok(Card::Ten),
-            // "J" => Ok(Card::Jack),
-            "J" => Ok(Card::Joker),
+            "J" => Ok(Card::Jack),
             "Q" => Ok(Card::Queen),
             "K" => Ok(Card::King),
             "A" => Ok(Card::Ace),
-            _ => Ok(Card::Number(s.parse().unwrap())),
+            _ => match s.parse() {
+                Ok(n @ 2..=9) => Ok(Card::Number(n)),
+                _ => Err(ParseError::new(
+                    s,
+                    "a card from 2 to 9 or one of T, J, Q, K, A",
+                )),
+            },
         }
     }
 }
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone)]
 enum HandType {
     HighCard,
     OnePair,
@@ -42,8 +48,8 @@ enum HandType {
     FiveOfAKind,
 }
 
-#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
-struct Hand {
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone)]
+pub struct Hand {
     hand_type: HandType,
     cards: [Card; 5],
     bid: u32,
@@ -57,7 +63,7 @@ impl Hand {
             match card {
                 Card::Number(n) => card_counts[*n as usize - 1] += 1,
                 Card::Ten => card_counts[9] += 1,
-                // Card::Jack => card_counts[10] += 1,
+                Card::Jack => card_counts[10] += 1,
                 Card::Joker => num_jokers += 1,
                 Card::Queen => card_counts[11] += 1,
                 Card::King => card_counts[12] += 1,
@@ -95,27 +101,50 @@ impl Hand {
 }
 
 impl FromStr for Hand {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (hand, bid) = s.split_once(" ").unwrap();
+        let (hand, bid) = split_once(s, " ")?;
+        if hand.chars().count() != 5 {
+            return Err(ParseError::new(hand, "a hand of 5 cards"));
+        }
+
         let mut cards = [Card::Number(0); 5];
-        for (i, card) in hand.chars().enumerate() {
-            cards[i] = card.to_string().parse().unwrap();
+        for (i, (start, card)) in hand.char_indices().enumerate() {
+            cards[i] = hand[start..start + card.len_utf8()].parse()?;
         }
 
         Ok(Self {
             hand_type: Self::analyze_hand_type(&cards),
             cards,
-            bid: bid.parse().unwrap(),
+            bid: parse_number(bid)?,
         })
     }
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    input
-        .lines()
-        .map(|l| l.parse::<Hand>().unwrap())
+impl Hand {
+    /// Re-scores the hand with every jack acting as a joker.
+    fn with_jokers(self) -> Self {
+        let cards = self.cards.map(|card| match card {
+            Card::Jack => Card::Joker,
+            card => card,
+        });
+
+        Self {
+            hand_type: Self::analyze_hand_type(&cards),
+            cards,
+            bid: self.bid,
+        }
+    }
+}
+
+pub fn parse(input: &str) -> Result<Vec<Hand>, ParseError> {
+    input.lines().map(|l| l.parse::<Hand>()).collect()
+}
+
+pub fn part_one(hands: &[Hand]) -> Option<u32> {
+    hands
+        .iter()
         .sorted()
         .enumerate()
         .map(|(i, h)| (i + 1) as u32 * h.bid)
@@ -123,10 +152,11 @@ pub fn part_one(input: &str) -> Option<u32> {
         .into()
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    input
-        .lines()
-        .map(|l| l.parse::<Hand>().unwrap())
+pub fn part_two(hands: &[Hand]) -> Option<u32> {
+    hands
+        .iter()
+        .cloned()
+        .map(Hand::with_jokers)
         .sorted()
         .enumerate()
         .map(|(i, h)| (i + 1) as u32 * h.bid)
@@ -140,13 +170,15 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        let result =
+            part_one(&parse(&advent_of_code::template::read_file("examples", DAY)).unwrap());
+        assert_eq!(result, Some(6440));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result =
+            part_two(&parse(&advent_of_code::template::read_file("examples", DAY)).unwrap());
         assert_eq!(result, Some(5905));
     }
 }
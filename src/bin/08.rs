@@ -1,8 +1,10 @@
+use advent_of_code::span;
+use advent_of_code::template::parse_error::{split_once, ParseError};
 use num::integer::lcm;
 use regex::Regex;
 use std::{collections::HashMap, str::FromStr};
 
-advent_of_code::solution!(8);
+advent_of_code::solution!(8, parse?);
 
 struct Node {
     left: String,
@@ -10,7 +12,7 @@ struct Node {
     name: String,
 }
 
-struct Network {
+pub struct Network {
     nodes: HashMap<String, Node>,
     path: String,
 }
@@ -45,7 +47,7 @@ impl Network {
         let mut path_position = 0usize;
         let mut nodes_steps_to_z: Vec<u64> = Vec::new();
 
-        while current_nodes.len() > 0 {
+        while !current_nodes.is_empty() {
             let mut next_nodes = Vec::new();
             let direction = self
                 .path
@@ -68,29 +70,39 @@ impl Network {
             path_position = (path_position + 1) % self.path.len();
             steps += 1;
         }
-        nodes_steps_to_z.iter().cloned().reduce(|a, b| lcm(a, b)).unwrap()
+        nodes_steps_to_z.iter().cloned().reduce(lcm).unwrap()
     }
 }
 
 impl FromStr for Network {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (path, nodes) = s.split_once("\n\n").unwrap();
-        let node_regex = Regex::new(r"([0-9A-Z]+) = \(([0-9A-Z]+), ([0-9A-Z]+)\)").unwrap();
+        let (path, nodes) = split_once(s, "\n\n")?;
+        if let Some((i, _)) = path.char_indices().find(|(_, c)| !matches!(c, 'L' | 'R')) {
+            return Err(ParseError::new(&path[i..i + 1], "a direction, L or R"));
+        }
+
+        let node_regex = span!(
+            "regex",
+            Regex::new(r"^([0-9A-Z]+) = \(([0-9A-Z]+), ([0-9A-Z]+)\)$").unwrap()
+        );
         let nodes = nodes.lines().map(|l| {
+            span!("node");
             let captures = node_regex
                 .captures(l)
-                .expect(format!("Invalid line {:?}", l).as_str());
+                .ok_or_else(|| ParseError::new(l, "a node like \"AAA = (BBB, CCC)\""))?;
             let (name, left, right) = (
                 captures[1].to_string(),
                 captures[2].to_string(),
                 captures[3].to_string(),
             );
-            Node { left, right, name }
+            Ok(Node { left, right, name })
         });
 
-        let nodes_map = HashMap::from_iter(nodes.map(|n| (n.name.clone(), n)));
+        let nodes_map = nodes
+            .map(|n| n.map(|n| (n.name.clone(), n)))
+            .collect::<Result<HashMap<_, _>, ParseError>>()?;
 
         Ok(Self {
             nodes: nodes_map,
@@ -99,13 +111,15 @@ impl FromStr for Network {
     }
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let network = input.parse::<Network>().unwrap();
+pub fn parse(input: &str) -> Result<Network, ParseError> {
+    input.parse::<Network>()
+}
+
+pub fn part_one(network: &Network) -> Option<u32> {
     Some(network.get_num_steps_to_zzz())
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let network = input.parse::<Network>().unwrap();
+pub fn part_two(network: &Network) -> Option<u64> {
     Some(network.get_num_steps_to_z_simultanious())
 }
 
@@ -115,17 +129,23 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file_part(
-            "examples", DAY, 1,
-        ));
+        let result = part_one(
+            &parse(&advent_of_code::template::read_file_part(
+                "examples", DAY, 1,
+            ))
+            .unwrap(),
+        );
         assert_eq!(result, Some(2));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 2,
-        ));
+        let result = part_two(
+            &parse(&advent_of_code::template::read_file_part(
+                "examples", DAY, 2,
+            ))
+            .unwrap(),
+        );
         assert_eq!(result, Some(6));
     }
 }
@@ -1,19 +1,24 @@
 use std::str::FromStr;
 
-advent_of_code::solution!(9);
+use advent_of_code::template::parse_error::{parse_number, ParseError};
+
+advent_of_code::solution!(9, parse?);
 
 struct ReportLine {
     values: Vec<i64>,
 }
 
 impl FromStr for ReportLine {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let values = s
             .split_whitespace()
-            .map(|s| s.parse::<i64>().expect("Failed to parse number"))
-            .collect::<Vec<_>>();
+            .map(parse_number::<i64>)
+            .collect::<Result<Vec<_>, _>>()?;
+        if values.is_empty() {
+            return Err(ParseError::new(s, "a list of numbers"));
+        }
         Ok(ReportLine { values })
     }
 }
@@ -39,30 +44,28 @@ impl ReportLine {
     fn predict_next(&self) -> i64 {
         let grid = self.build_grid();
 
-        let res = (0..grid.len()).map(|i| grid[i][i]).sum();
-        res
+        (0..grid.len()).map(|i| grid[i][i]).sum()
     }
 
     fn predict_previous(&self) -> i64 {
         let grid = self.build_grid();
 
-        let res = (0..grid.len()).fold(0, |acc, i| grid[i][0] - acc);
-        res
+        (0..grid.len()).fold(0, |acc, i| grid[i][0] - acc)
     }
 }
 
-struct Report {
+pub struct Report {
     lines: Vec<ReportLine>,
 }
 
 impl FromStr for Report {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         let lines = s
             .lines()
-            .map(|line| line.parse::<ReportLine>().expect("Failed to parse line"))
-            .collect::<Vec<_>>();
+            .map(|line| line.parse::<ReportLine>())
+            .collect::<Result<Vec<_>, _>>()?;
         Ok(Report { lines })
     }
 }
@@ -77,13 +80,15 @@ impl Report {
     }
 }
 
-pub fn part_one(input: &str) -> Option<i64> {
-    let report = input.parse::<Report>().expect("Failed to parse report");
+pub fn parse(input: &str) -> Result<Report, ParseError> {
+    input.parse::<Report>()
+}
+
+pub fn part_one(report: &Report) -> Option<i64> {
     Some(report.get_sum_next())
 }
 
-pub fn part_two(input: &str) -> Option<i64> {
-    let report = input.parse::<Report>().expect("Failed to parse report");
+pub fn part_two(report: &Report) -> Option<i64> {
     Some(report.get_sum_previous())
 }
 
@@ -93,13 +98,15 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result =
+            part_one(&parse(&advent_of_code::template::read_file("examples", DAY)).unwrap());
         assert_eq!(result, Some(114));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result =
+            part_two(&parse(&advent_of_code::template::read_file("examples", DAY)).unwrap());
         assert_eq!(result, Some(2));
     }
 }
@@ -1,8 +1,13 @@
 use std::str::FromStr;
 
-advent_of_code::solution!(10);
+use advent_of_code::template::{
+    parse_error::ParseError,
+    visualize::{self, Color, Frame},
+};
 
-struct Map {
+advent_of_code::solution!(10, parse?);
+
+pub struct Map {
     map: Vec<Vec<char>>,
 }
 
@@ -97,10 +102,23 @@ impl Map {
             }
             prev = pos;
             loop_len += 1;
+
+            if loop_len % 100 == 0 {
+                visualize::frame(|| self.frame(&vertices, cur, loop_len));
+            }
         }
+        visualize::frame(|| self.frame(&vertices, cur, loop_len));
         (loop_len, vertices)
     }
 
+    /// The map with the corners of the loop found so far, and the current tile.
+    fn frame(&self, vertices: &[(usize, usize)], cur: (usize, usize), loop_len: u32) -> Frame {
+        Frame::from_fn(self.map[0].len(), self.map.len(), |x, y| self.map[y][x])
+            .highlight_all(vertices.iter().map(|&(i, j)| (j, i)), Color::Yellow)
+            .highlight(cur.1, cur.0, Color::Red)
+            .caption(format!("{loop_len} tiles"))
+    }
+
     fn get_max_dist_from_s(&self) -> u32 {
         self.get_loop_length_and_vertices().0 / 2
     }
@@ -110,19 +128,23 @@ impl Map {
         let mut surface = 0;
         // use shoelace formula
         for i in 0..vertices.len() {
-            let x1 = vertices[i as usize].1 as i32;
-            let y1 = vertices[i as usize].0 as i32;
-            let x2 = vertices[(i + 1) as usize % vertices.len() as usize].1 as i32;
-            let y2 = vertices[(i + 1) as usize % vertices.len() as usize].0 as i32;
+            let x1 = vertices[i].1 as i32;
+            let y1 = vertices[i].0 as i32;
+            let x2 = vertices[(i + 1) % vertices.len()].1 as i32;
+            let y2 = vertices[(i + 1) % vertices.len()].0 as i32;
             surface += x1 * y2 - x2 * y1;
         }
-        surface.abs() as u32 / 2 - loop_len / 2 + 1
+        surface.unsigned_abs() / 2 - loop_len / 2 + 1
     }
 }
 impl FromStr for Map {
-    type Err = ();
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.matches('S').count() != 1 {
+            return Err(ParseError::new(s, "a map with exactly one start tile"));
+        }
+
         let map = s
             .lines()
             .map(|line| line.chars().collect::<Vec<_>>())
@@ -130,13 +152,15 @@ impl FromStr for Map {
         Ok(Map { map })
     }
 }
-pub fn part_one(input: &str) -> Option<u32> {
-    let map = input.parse::<Map>().expect("Failed to parse map");
+pub fn parse(input: &str) -> Result<Map, ParseError> {
+    input.parse::<Map>()
+}
+
+pub fn part_one(map: &Map) -> Option<u32> {
     Some(map.get_max_dist_from_s())
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let map = input.parse::<Map>().expect("Failed to parse map");
+pub fn part_two(map: &Map) -> Option<u32> {
     Some(map.get_surface())
 }
 
@@ -146,17 +170,23 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file_part(
-            "examples", DAY, 1,
-        ));
+        let result = part_one(
+            &parse(&advent_of_code::template::read_file_part(
+                "examples", DAY, 1,
+            ))
+            .unwrap(),
+        );
         assert_eq!(result, Some(8));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 2,
-        ));
+        let result = part_two(
+            &parse(&advent_of_code::template::read_file_part(
+                "examples", DAY, 2,
+            ))
+            .unwrap(),
+        );
         assert_eq!(result, Some(10));
     }
 }
@@ -1,6 +1,8 @@
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
 use std::error::Error;
 use std::fmt::Display;
 use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 /// A valid day number of advent (i.e. an integer in range 1 to 25).
 ///
@@ -35,8 +37,51 @@ impl Day {
     pub fn into_inner(self) -> u8 {
         self.0
     }
+
+    /// The instant the puzzle of this day unlocks in `year`, which is midnight US Eastern time on the day of
+    /// December.
+    ///
+    /// ```
+    /// # use advent_of_code::Day;
+    /// # use std::time::{Duration, UNIX_EPOCH};
+    /// let day = Day::new(1).unwrap();
+    /// // 2023-12-01 05:00 UTC.
+    /// assert_eq!(day.unlock_time(2023), UNIX_EPOCH + Duration::from_secs(1_701_406_800));
+    /// ```
+    pub fn unlock_time(self, year: u16) -> SystemTime {
+        // days since the unix epoch, see http://howardhinnant.github.io/date_algorithms.html#days_from_civil.
+        let year = i64::from(year);
+        let era = year.div_euclid(400);
+        let year_of_era = year - era * 400;
+        // days from the 1st of March, which the algorithm starts its years with.
+        let day_of_year = 275 + i64::from(self.0) - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        let days = era * 146_097 + day_of_era - 719_468;
+
+        let seconds = days * 86_400 + UNLOCK_OFFSET_HOURS * 3600;
+        match u64::try_from(seconds) {
+            Ok(seconds) => UNIX_EPOCH + Duration::from_secs(seconds),
+            Err(_) => UNIX_EPOCH - Duration::from_secs(seconds.unsigned_abs()),
+        }
+    }
+
+    /// Whether the puzzle of this day is unlocked in `year` at the time of `clock`.
+    pub fn is_unlocked(self, year: u16, clock: &impl Clock) -> bool {
+        clock.now() >= self.unlock_time(year)
+    }
+
+    /// The time until the puzzle of this day unlocks in `year`, or `None` if it is already unlocked.
+    pub fn time_until_unlock(self, year: u16, clock: &impl Clock) -> Option<Duration> {
+        self.unlock_time(year)
+            .duration_since(clock.now())
+            .ok()
+            .filter(|duration| !duration.is_zero())
+    }
 }
 
+/// Midnight US Eastern time, which is always standard time (UTC-5) in December, in UTC.
+const UNLOCK_OFFSET_HOURS: i64 = 5;
+
 impl Display for Day {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "{:02}", self.0)
@@ -80,6 +125,21 @@ impl Display for DayFromStrError {
 
 /* -------------------------------------------------------------------------- */
 
+impl Serialize for Day {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u8(self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for Day {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let day = u8::deserialize(deserializer)?;
+        Self::new(day).ok_or_else(|| de::Error::custom(DayFromStrError))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
 /// An iterator that yields every day of advent from the 1st to the 25th.
 pub fn all_days() -> AllDays {
     AllDays::new()
@@ -114,6 +174,64 @@ impl Iterator for AllDays {
 
 /* -------------------------------------------------------------------------- */
 
+/// A source of the current time, which can be replaced in tests.
+pub trait Clock {
+    fn now(&self) -> SystemTime;
+}
+
+/// The clock of the operating system.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+}
+
+/// A fixed instant is a clock that stands still.
+impl Clock for SystemTime {
+    fn now(&self) -> SystemTime {
+        *self
+    }
+}
+
+/// An iterator that yields every day of advent whose puzzle is unlocked in `year`.
+pub fn unlocked_days(year: u16) -> UnlockedDays {
+    unlocked_days_at(year, &SystemClock)
+}
+
+/// An iterator that yields every day of advent whose puzzle is unlocked in `year` at the time of `clock`.
+pub fn unlocked_days_at(year: u16, clock: &impl Clock) -> UnlockedDays {
+    UnlockedDays {
+        days: all_days(),
+        year,
+        now: clock.now(),
+    }
+}
+
+/// An iterator that yields every day of advent whose puzzle is unlocked in a year.
+pub struct UnlockedDays {
+    days: AllDays,
+    year: u16,
+    /// The time is read once, so that a day that unlocks during the iteration is either yielded with every
+    /// day before it or not at all.
+    now: SystemTime,
+}
+
+impl Iterator for UnlockedDays {
+    type Item = Day;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // days unlock in order, so no later day is unlocked after the first locked one.
+        self.days
+            .next()
+            .filter(|day| day.is_unlocked(self.year, &self.now))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
 /// Creates a [`Day`] value in a const context.
 #[macro_export]
 macro_rules! day {
@@ -134,7 +252,8 @@ macro_rules! day {
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{all_days, Day};
+    use super::{all_days, unlocked_days_at, Day};
+    use std::time::{Duration, UNIX_EPOCH};
 
     #[test]
     fn all_days_iterator() {
@@ -167,6 +286,40 @@ mod tests {
         assert_eq!(iter.next(), Some(Day(25)));
         assert_eq!(iter.next(), None);
     }
+
+    #[test]
+    fn unlock_time() {
+        let at = |seconds| UNIX_EPOCH + Duration::from_secs(seconds);
+
+        // 2015-12-01 05:00 UTC, 2023-12-25 05:00 UTC.
+        assert_eq!(Day(1).unlock_time(2015), at(1_448_946_000));
+        assert_eq!(Day(25).unlock_time(2023), at(1_703_480_400));
+
+        let unlock = Day(12).unlock_time(2023);
+        assert!(!Day(12).is_unlocked(2023, &(unlock - Duration::from_secs(1))));
+        assert!(Day(12).is_unlocked(2023, &unlock));
+        assert_eq!(
+            Day(12).time_until_unlock(2023, &(unlock - Duration::from_secs(90))),
+            Some(Duration::from_secs(90))
+        );
+        assert_eq!(Day(12).time_until_unlock(2023, &unlock), None);
+    }
+
+    #[test]
+    fn unlocked_days_iterator() {
+        let unlock = Day(3).unlock_time(2023);
+
+        assert_eq!(
+            unlocked_days_at(2023, &unlock).collect::<Vec<_>>(),
+            vec![Day(1), Day(2), Day(3)]
+        );
+        assert_eq!(
+            unlocked_days_at(2023, &(unlock - Duration::from_secs(1))).count(),
+            2
+        );
+        assert_eq!(unlocked_days_at(2024, &unlock).count(), 0);
+        assert_eq!(unlocked_days_at(2022, &unlock).count(), 25);
+    }
 }
 
 /* -------------------------------------------------------------------------- */
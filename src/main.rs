@@ -1,31 +1,104 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve};
+use advent_of_code::template::commands::{
+    all, bench_history, download, examples, read, scaffold, solve,
+};
 use args::{parse, AppArguments};
 
+/// Every scaffolded day, compiled into this binary so that `all` can run them in-process.
+mod solutions {
+    include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+}
+
 mod args {
     use std::process;
 
-    use advent_of_code::Day;
+    use advent_of_code::{
+        template::{
+            bench_history::DEFAULT_THRESHOLD,
+            commands::{all::Baselines, RunOptions},
+            inputs::{InputSelection, InputSource},
+            log::Level,
+        },
+        Day,
+    };
 
     pub enum AppArguments {
         Download {
             day: Day,
+            wait: bool,
+        },
+        Examples {
+            day: Day,
+            part: Option<u8>,
+            block: Option<usize>,
+            list: bool,
         },
         Read {
             day: Day,
+            part: Option<u8>,
+            no_pager: bool,
         },
         Scaffold {
             day: Day,
         },
         Solve {
             day: Day,
-            release: bool,
-            time: bool,
             submit: Option<u8>,
+            watch: bool,
+            options: RunOptions,
         },
         All {
-            release: bool,
-            time: bool,
+            require_answers: bool,
+            jobs: usize,
+            options: RunOptions,
+            baselines: Baselines,
         },
+        BenchHistory {
+            day: Option<Day>,
+            baseline: Option<String>,
+            threshold: f64,
+        },
+    }
+
+    /// Parse `--all-inputs`, `--input-name <name>` and, if `allow_path` is set, `--input <path|->`.
+    fn parse_inputs(
+        args: &mut pico_args::Arguments,
+        allow_path: bool,
+    ) -> Result<InputSelection, pico_args::Error> {
+        if args.contains("--all-inputs") {
+            return Ok(InputSelection::All);
+        }
+
+        if let Some(source) = args.opt_value_from_fn("--input-name", InputSource::named)? {
+            return Ok(InputSelection::Single(source));
+        }
+
+        if allow_path {
+            if let Some(path) = args.opt_value_from_str::<_, String>("--input")? {
+                return Ok(InputSelection::from_path(&path));
+            }
+        }
+
+        Ok(InputSelection::Default)
+    }
+
+    /// Parse the flags shared by `solve` and `all`.
+    fn parse_run_options(
+        args: &mut pico_args::Arguments,
+        allow_path: bool,
+    ) -> Result<RunOptions, pico_args::Error> {
+        Ok(RunOptions {
+            release: args.contains("--release"),
+            time: args.contains("--time"),
+            format: args.opt_value_from_str("--format")?.unwrap_or_default(),
+            timeout: args.opt_value_from_str("--timeout")?,
+            memory: args.contains("--memory"),
+            spans: args.contains("--spans"),
+            visualize: false,
+            export: None,
+            fps: None,
+            log_level: None,
+            inputs: parse_inputs(args, allow_path)?,
+        })
     }
 
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
@@ -33,13 +106,34 @@ mod args {
 
         let app_args = match args.subcommand()?.as_deref() {
             Some("all") => AppArguments::All {
-                release: args.contains("--release"),
-                time: args.contains("--time"),
+                require_answers: args.contains("--require-answers"),
+                jobs: args.opt_value_from_str(["-j", "--jobs"])?.unwrap_or(1),
+                options: parse_run_options(&mut args, false)?,
+                baselines: Baselines {
+                    save: args.opt_value_from_str("--save-baseline")?,
+                    compare: args.opt_value_from_str("--baseline")?,
+                },
+            },
+            Some("bench-history") => AppArguments::BenchHistory {
+                baseline: args.opt_value_from_str("--baseline")?,
+                threshold: args
+                    .opt_value_from_str("--threshold")?
+                    .unwrap_or(DEFAULT_THRESHOLD),
+                day: args.opt_free_from_str()?,
             },
             Some("download") => AppArguments::Download {
+                wait: args.contains("--wait"),
+                day: args.free_from_str()?,
+            },
+            Some("examples") => AppArguments::Examples {
+                part: args.opt_value_from_str("--part")?,
+                block: args.opt_value_from_str("--block")?,
+                list: args.contains("--list"),
                 day: args.free_from_str()?,
             },
             Some("read") => AppArguments::Read {
+                part: args.opt_value_from_str("--part")?,
+                no_pager: args.contains("--no-pager"),
                 day: args.free_from_str()?,
             },
             Some("scaffold") => AppArguments::Scaffold {
@@ -47,9 +141,18 @@ mod args {
             },
             Some("solve") => AppArguments::Solve {
                 day: args.free_from_str()?,
-                release: args.contains("--release"),
                 submit: args.opt_value_from_str("--submit")?,
-                time: args.contains("--time"),
+                watch: args.contains("--watch"),
+                options: RunOptions {
+                    visualize: args.contains("--visualize"),
+                    export: args.opt_value_from_str("--export")?,
+                    fps: args.opt_value_from_str("--fps")?,
+                    log_level: match args.opt_value_from_str("--log-level")? {
+                        Some(level) => Some(level),
+                        None => args.contains("--verbose").then_some(Level::Debug),
+                    },
+                    ..parse_run_options(&mut args, true)?
+                },
             },
             Some(x) => {
                 eprintln!("Unknown command: {x}");
@@ -77,16 +180,42 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release, time } => all::handle(release, time),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
+            AppArguments::All {
+                require_answers,
+                jobs,
+                options,
+                baselines,
+            } => all::handle(
+                solutions::SOLUTIONS,
+                require_answers,
+                jobs,
+                &options,
+                &baselines,
+            ),
+            AppArguments::BenchHistory {
+                day,
+                baseline,
+                threshold,
+            } => bench_history::handle(day, baseline.as_deref(), threshold),
+            AppArguments::Download { day, wait } => download::handle(day, wait),
+            AppArguments::Examples {
+                day,
+                part,
+                block,
+                list,
+            } => examples::handle(day, part, block, list),
+            AppArguments::Read {
+                day,
+                part,
+                no_pager,
+            } => read::handle(day, part, no_pager),
             AppArguments::Scaffold { day } => scaffold::handle(day),
             AppArguments::Solve {
                 day,
-                release,
-                time,
                 submit,
-            } => solve::handle(day, release, time, submit),
+                watch,
+                options,
+            } => solve::handle(day, submit, watch, &options),
         },
     };
 }
@@ -0,0 +1,124 @@
+/// Module that stores accepted answers in `data/answers/DD.toml`, so that solutions can be checked for regressions.
+/// Answers are saved automatically after a successful submission, but the files can also be edited by hand:
+///
+/// ```toml
+/// part_one = "35"
+/// part_two = "46"
+/// ```
+use std::{fmt::Display, fs, io, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+use crate::Day;
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(toml::de::Error),
+    IO(io::Error),
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl From<toml::de::Error> for Error {
+    fn from(e: toml::de::Error) -> Self {
+        Error::Parser(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(e) => write!(f, "could not parse answers file: {e}"),
+            Error::IO(e) => write!(f, "could not access answers file: {e}"),
+        }
+    }
+}
+
+/// The accepted answers of a day.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Answers {
+    pub part_one: Option<String>,
+    pub part_two: Option<String>,
+}
+
+impl Answers {
+    #[must_use]
+    pub fn get(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_one.as_deref(),
+            2 => self.part_two.as_deref(),
+            _ => None,
+        }
+    }
+
+    pub fn set(&mut self, part: u8, answer: &str) {
+        match part {
+            1 => self.part_one = Some(answer.into()),
+            2 => self.part_two = Some(answer.into()),
+            _ => {}
+        }
+    }
+}
+
+#[must_use]
+pub fn get_path(day: Day) -> String {
+    format!("data/answers/{day}.toml")
+}
+
+/// Read the stored answers of a day. A missing file means that no answers are known yet.
+pub fn read(day: Day) -> Result<Answers, Error> {
+    read_from(&get_path(day))
+}
+
+/// Store the accepted answer of one part, keeping the answer of the other part intact.
+pub fn save(day: Day, part: u8, answer: &str) -> Result<(), Error> {
+    save_to(&get_path(day), part, answer)
+}
+
+pub(crate) fn read_from(path: &str) -> Result<Answers, Error> {
+    match fs::read_to_string(path) {
+        Ok(s) => Ok(toml::from_str(&s)?),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Answers::default()),
+        Err(e) => Err(e.into()),
+    }
+}
+
+pub(crate) fn save_to(path: &str, part: u8, answer: &str) -> Result<(), Error> {
+    let mut answers = read_from(path)?;
+    answers.set(part, answer);
+
+    if let Some(dir) = Path::new(path).parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    fs::write(path, toml::to_string(&answers).unwrap())?;
+    Ok(())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Answers;
+
+    #[test]
+    fn parses_partial_answers() {
+        let answers: Answers = toml::from_str("part_two = \"46\"\n").unwrap();
+        assert_eq!(answers.get(1), None);
+        assert_eq!(answers.get(2), Some("46"));
+    }
+
+    #[test]
+    fn sets_answers() {
+        let mut answers = Answers::default();
+        answers.set(1, "35");
+        answers.set(2, "46");
+        answers.set(1, "36");
+        assert_eq!(
+            toml::to_string(&answers).unwrap(),
+            "part_one = \"36\"\npart_two = \"46\"\n"
+        );
+    }
+}
@@ -1,15 +1,35 @@
 /// Wrapper module around the "aoc-cli" command-line.
+/// The output of aoc-cli is captured, so that failures can be reported with a message that says how to fix them.
 use std::{
     fmt::Display,
-    process::{Command, Output, Stdio},
+    io,
+    process::{Command, Output},
 };
 
-use crate::Day;
+use crate::{template::aoc_year, Day};
+
+/// The version of aoc-cli that the wrapper is written against. Other minor versions may change its arguments.
+pub const SUPPORTED_VERSION: &str = "0.12";
+
+const INSTALL_HINT: &str = "Run \"cargo install aoc-cli --version 0.12.0\" to install it.";
 
 #[derive(Debug)]
 pub enum AocCommandError {
     CommandNotFound,
     CommandNotCallable,
+    /// aoc-cli found no session cookie.
+    MissingSession,
+    /// The website rejected the session cookie.
+    ExpiredSession,
+    /// The puzzle exists, but is not unlocked yet.
+    NotUnlocked,
+    /// The website answered with 404, usually as `AOC_YEAR` is not a year of Advent of Code.
+    InvalidYear,
+    /// The website could not be reached. Contains the error that aoc-cli reported.
+    Network(String),
+    /// The installed aoc-cli reports this version rather than [`SUPPORTED_VERSION`].
+    VersionMismatch(String),
+    /// aoc-cli failed for a reason that is not recognized.
     BadExitStatus(Output),
     IoError,
 }
@@ -17,22 +37,47 @@ pub enum AocCommandError {
 impl Display for AocCommandError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            AocCommandError::CommandNotFound => write!(f, "aoc-cli is not present in environment."),
+            AocCommandError::CommandNotFound => {
+                write!(f, "aoc-cli is not present in environment. {INSTALL_HINT}")
+            }
             AocCommandError::CommandNotCallable => write!(f, "aoc-cli could not be called."),
-            AocCommandError::BadExitStatus(_) => {
-                write!(f, "aoc-cli exited with a non-zero status.")
+            AocCommandError::MissingSession => write!(
+                f,
+                "aoc-cli found no session cookie. Save it to \"~/.adventofcode.session\"."
+            ),
+            AocCommandError::ExpiredSession => write!(
+                f,
+                "the session cookie was rejected, it may have expired. Refresh \"~/.adventofcode.session\"."
+            ),
+            AocCommandError::NotUnlocked => write!(f, "the puzzle is not unlocked yet."),
+            AocCommandError::InvalidYear => write!(
+                f,
+                "the puzzle was not found. Check that AOC_YEAR in \".cargo/config.toml\" is a year of Advent of Code."
+            ),
+            AocCommandError::Network(e) => {
+                write!(f, "could not reach the website, check your connection: {e}")
+            }
+            AocCommandError::VersionMismatch(version) => write!(
+                f,
+                "aoc-cli {version} is installed, but version {SUPPORTED_VERSION} is required. {INSTALL_HINT}"
+            ),
+            AocCommandError::BadExitStatus(output) => {
+                write!(f, "aoc-cli exited with a non-zero status.")?;
+                match last_line(output) {
+                    Some(line) => write!(f, " {line}"),
+                    None => Ok(()),
+                }
             }
             AocCommandError::IoError => write!(f, "could not write output files to file system."),
         }
     }
 }
 
+impl std::error::Error for AocCommandError {}
+
+/// Check that aoc-cli is installed in a supported version.
 pub fn check() -> Result<(), AocCommandError> {
-    Command::new("aoc")
-        .arg("-V")
-        .output()
-        .map_err(|_| AocCommandError::CommandNotFound)?;
-    Ok(())
+    check_command(aoc_command())
 }
 
 pub fn read(day: Day) -> Result<Output, AocCommandError> {
@@ -79,6 +124,7 @@ pub fn submit(day: Day, part: u8, result: &str) -> Result<Output, AocCommandErro
     let mut args = build_args("submit", &[], day);
     args.push(part.to_string());
     args.push(result.to_string());
+
     call_aoc_cli(&args)
 }
 
@@ -90,17 +136,10 @@ fn get_puzzle_path(day: Day) -> String {
     format!("data/puzzles/{day}.md")
 }
 
-fn get_year() -> Option<u16> {
-    match std::env::var("AOC_YEAR") {
-        Ok(x) => x.parse().ok().or(None),
-        Err(_) => None,
-    }
-}
-
 fn build_args(command: &str, args: &[String], day: Day) -> Vec<String> {
     let mut cmd_args = args.to_vec();
 
-    if let Some(year) = get_year() {
+    if let Some(year) = aoc_year() {
         cmd_args.push("--year".into());
         cmd_args.push(year.to_string());
     }
@@ -110,18 +149,207 @@ fn build_args(command: &str, args: &[String], day: Day) -> Vec<String> {
     cmd_args
 }
 
+fn aoc_command() -> Command {
+    Command::new("aoc")
+}
+
+fn check_command(mut command: Command) -> Result<(), AocCommandError> {
+    let output = command.arg("-V").output().map_err(|e| match e.kind() {
+        io::ErrorKind::NotFound => AocCommandError::CommandNotFound,
+        _ => AocCommandError::CommandNotCallable,
+    })?;
+
+    if !output.status.success() {
+        return Err(AocCommandError::CommandNotCallable);
+    }
+
+    // e.g. "aoc-cli 0.12.0".
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let version = stdout.split_whitespace().last().unwrap_or_default();
+
+    if version == SUPPORTED_VERSION || version.starts_with(&format!("{SUPPORTED_VERSION}.")) {
+        Ok(())
+    } else {
+        Err(AocCommandError::VersionMismatch(version.into()))
+    }
+}
+
 fn call_aoc_cli(args: &[String]) -> Result<Output, AocCommandError> {
-    // println!("Calling >aoc with: {}", args.join(" "));
-    let output = Command::new("aoc")
+    call_command(aoc_command(), args)
+}
+
+/// Run aoc-cli and forward its output once it exits successfully, or classify the failure otherwise.
+fn call_command(mut command: Command, args: &[String]) -> Result<Output, AocCommandError> {
+    let output = command
         .args(args)
-        .stdout(Stdio::inherit())
-        .stderr(Stdio::inherit())
         .output()
         .map_err(|_| AocCommandError::CommandNotCallable)?;
 
     if output.status.success() {
+        print!("{}", String::from_utf8_lossy(&output.stdout));
+        eprint!("{}", String::from_utf8_lossy(&output.stderr));
         Ok(output)
     } else {
-        Err(AocCommandError::BadExitStatus(output))
+        Err(classify(output))
+    }
+}
+
+/// Map the output of a failed call to the error that explains it, going by the messages of aoc-cli.
+fn classify(output: Output) -> AocCommandError {
+    let text = format!(
+        "{}\n{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    )
+    .to_lowercase();
+    let mentions = |patterns: &[&str]| patterns.iter().any(|pattern| text.contains(pattern));
+
+    if mentions(&[
+        "session cookie file not found",
+        "failed to read session cookie",
+    ]) {
+        AocCommandError::MissingSession
+    } else if mentions(&[
+        "invalid session cookie",
+        "400 bad request",
+        "401 unauthorized",
+    ]) {
+        AocCommandError::ExpiredSession
+    } else if mentions(&["still locked", "not unlocked"]) {
+        AocCommandError::NotUnlocked
+    } else if mentions(&["not a valid advent of code year", "404 not found"]) {
+        AocCommandError::InvalidYear
+    } else if mentions(&[
+        "error sending request",
+        "dns error",
+        "connection refused",
+        "timed out",
+    ]) {
+        AocCommandError::Network(last_line(&output).unwrap_or_default())
+    } else {
+        AocCommandError::BadExitStatus(output)
+    }
+}
+
+/// The last line that aoc-cli wrote to stderr, or else to stdout.
+fn last_line(output: &Output) -> Option<String> {
+    [&output.stderr, &output.stdout]
+        .into_iter()
+        .find_map(|stream| {
+            String::from_utf8_lossy(stream)
+                .lines()
+                .rev()
+                .find(|line| !line.trim().is_empty())
+                .map(|line| line.trim().to_string())
+        })
+}
+
+#[cfg(all(feature = "test_lib", unix))]
+mod tests {
+    use super::{call_command, check_command, AocCommandError};
+    use std::{
+        env, fs,
+        os::unix::fs::PermissionsExt,
+        path::PathBuf,
+        process::Command,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    /// An `aoc` executable that runs `script`, which is only on the `PATH` of the commands it creates.
+    struct FakeAoc {
+        dir: PathBuf,
+    }
+
+    impl FakeAoc {
+        fn new(script: &str) -> Self {
+            static COUNT: AtomicUsize = AtomicUsize::new(0);
+            let dir = env::temp_dir().join(format!(
+                "aoc-cli-{}-{}",
+                std::process::id(),
+                COUNT.fetch_add(1, Ordering::Relaxed)
+            ));
+            fs::create_dir_all(&dir).unwrap();
+
+            let path = dir.join("aoc");
+            fs::write(&path, format!("#!/bin/sh\n{script}\n")).unwrap();
+            fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+
+            Self { dir }
+        }
+
+        fn command(&self) -> Command {
+            let mut command = Command::new("aoc");
+            let path = env::var("PATH").unwrap_or_default();
+            command.env("PATH", format!("{}:{path}", self.dir.display()));
+            command
+        }
+    }
+
+    impl Drop for FakeAoc {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.dir);
+        }
+    }
+
+    fn failure(message: &str) -> AocCommandError {
+        let script = format!("echo '[ERROR aoc] 🔔 {message}' >&2; exit 2");
+        call_command(FakeAoc::new(&script).command(), &["read".into()]).unwrap_err()
+    }
+
+    #[test]
+    fn classifies_failures() {
+        assert!(matches!(
+            failure("Session cookie file not found in home or config directory"),
+            AocCommandError::MissingSession
+        ));
+        assert!(matches!(
+            failure("HTTP status client error (400 Bad Request) for url (https://adventofcode.com/2023/day/1/input)"),
+            AocCommandError::ExpiredSession
+        ));
+        assert!(matches!(
+            failure("Puzzle 25 of 2023 is still locked"),
+            AocCommandError::NotUnlocked
+        ));
+        assert!(matches!(
+            failure("HTTP status client error (404 Not Found) for url (https://adventofcode.com/2003/day/1)"),
+            AocCommandError::InvalidYear
+        ));
+        assert!(matches!(
+            failure("HTTP request error: error sending request for url (https://adventofcode.com/2023/day/1)"),
+            AocCommandError::Network(e) if e.ends_with("(https://adventofcode.com/2023/day/1)")
+        ));
+
+        let error = failure("Something else");
+        assert!(matches!(error, AocCommandError::BadExitStatus(_)));
+        assert_eq!(
+            error.to_string(),
+            "aoc-cli exited with a non-zero status. [ERROR aoc] 🔔 Something else"
+        );
+    }
+
+    #[test]
+    fn forwards_output() {
+        let aoc = FakeAoc::new("echo \"$@\"");
+        let output = call_command(aoc.command(), &["read".into()]).unwrap();
+        assert_eq!(String::from_utf8_lossy(&output.stdout), "read\n");
+    }
+
+    #[test]
+    fn checks_version() {
+        let check = |script: &str| check_command(FakeAoc::new(script).command());
+
+        assert!(check("echo 'aoc-cli 0.12.0'").is_ok());
+        assert!(matches!(
+            check("echo 'aoc-cli 0.11.1'"),
+            Err(AocCommandError::VersionMismatch(version)) if version == "0.11.1"
+        ));
+        assert!(matches!(
+            check("exit 1"),
+            Err(AocCommandError::CommandNotCallable)
+        ));
+        assert!(matches!(
+            check_command(Command::new("aoc-cli-that-does-not-exist")),
+            Err(AocCommandError::CommandNotFound)
+        ));
     }
 }
@@ -0,0 +1,512 @@
+/// Conversion of the HTML of puzzle descriptions into markdown, and of that markdown into text for the terminal.
+/// Puzzles only use a handful of tags, so this is a small tree builder and renderer rather than a full HTML parser.
+use std::slice;
+
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+/// A node of the parsed HTML tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Node {
+    Text(String),
+    Element {
+        name: String,
+        /// The target of links, the only attribute that is rendered.
+        href: Option<String>,
+        children: Vec<Node>,
+    },
+}
+
+/// Elements that never have children or a closing tag.
+const VOID_ELEMENTS: [&str; 6] = ["br", "hr", "img", "input", "meta", "link"];
+
+/// Extract the `<article>` elements of `html` and convert them into markdown, separated by blank lines.
+/// Puzzle pages contain one article per unlocked part, responses to submissions contain one with the verdict.
+#[must_use]
+pub fn articles_to_markdown(html: &str) -> Option<String> {
+    let articles: Vec<String> = find_elements(&parse(html), "article")
+        .into_iter()
+        .map(|article| to_markdown(slice::from_ref(article)))
+        .collect();
+
+    (!articles.is_empty()).then(|| articles.join("\n\n"))
+}
+
+/// Convert an HTML fragment into markdown.
+#[must_use]
+pub fn html_to_markdown(html: &str) -> String {
+    to_markdown(&parse(html))
+}
+
+fn to_markdown(nodes: &[Node]) -> String {
+    let mut markdown = String::new();
+    render_blocks(nodes, &mut markdown);
+
+    // block elements each end in a blank line, which may add up when they are nested.
+    let mut result = String::new();
+    let mut newlines = 0;
+
+    for c in markdown.trim().chars() {
+        newlines = if c == '\n' { newlines + 1 } else { 0 };
+        if newlines <= 2 {
+            result.push(c);
+        }
+    }
+
+    result
+}
+
+fn parse(html: &str) -> Vec<Node> {
+    // every open element along with the children collected so far, the document root first.
+    let mut stack: Vec<(String, Option<String>, Vec<Node>)> = vec![(String::new(), None, vec![])];
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        let Some(start) = rest.find('<') else {
+            push_text(&mut stack, rest);
+            break;
+        };
+
+        push_text(&mut stack, &rest[..start]);
+        rest = &rest[start..];
+
+        // comments and doctypes are skipped.
+        if let Some(comment) = rest.strip_prefix("<!--") {
+            rest = comment.find("-->").map_or("", |end| &comment[end + 3..]);
+            continue;
+        }
+
+        let Some(end) = rest.find('>') else {
+            push_text(&mut stack, rest);
+            break;
+        };
+        let tag = &rest[1..end];
+        rest = &rest[end + 1..];
+
+        if tag.starts_with('!') {
+            continue;
+        }
+
+        if let Some(name) = tag.strip_prefix('/') {
+            let name = name.trim().to_ascii_lowercase();
+            // close the innermost element with that name, along with unclosed elements inside of it.
+            if let Some(depth) = stack.iter().rposition(|(open, ..)| *open == name) {
+                while stack.len() > depth.max(1) {
+                    close_element(&mut stack);
+                }
+            }
+            continue;
+        }
+
+        let is_self_closing = tag.ends_with('/');
+        let tag = tag.trim_end_matches('/');
+        let name = tag
+            .split_whitespace()
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        let href = attribute(tag, "href");
+
+        if is_self_closing || VOID_ELEMENTS.contains(&name.as_str()) {
+            push_node(
+                &mut stack,
+                Node::Element {
+                    name,
+                    href,
+                    children: vec![],
+                },
+            );
+        } else {
+            stack.push((name, href, vec![]));
+        }
+    }
+
+    while stack.len() > 1 {
+        close_element(&mut stack);
+    }
+
+    stack
+        .pop()
+        .map(|(.., children)| children)
+        .unwrap_or_default()
+}
+
+fn push_node(stack: &mut [(String, Option<String>, Vec<Node>)], node: Node) {
+    if let Some((.., children)) = stack.last_mut() {
+        children.push(node);
+    }
+}
+
+fn push_text(stack: &mut [(String, Option<String>, Vec<Node>)], text: &str) {
+    if !text.is_empty() {
+        push_node(stack, Node::Text(decode_entities(text)));
+    }
+}
+
+fn close_element(stack: &mut Vec<(String, Option<String>, Vec<Node>)>) {
+    if let Some((name, href, children)) = stack.pop() {
+        push_node(
+            stack,
+            Node::Element {
+                name,
+                href,
+                children,
+            },
+        );
+    }
+}
+
+/// The value of a quoted attribute of a tag, e.g. `href="/2023/day/1"`.
+fn attribute(tag: &str, name: &str) -> Option<String> {
+    let start = tag.find(&format!(" {name}="))? + name.len() + 2;
+    let value = &tag[start..];
+    let quote = value.chars().next().filter(|c| matches!(c, '"' | '\''))?;
+    let value = &value[1..];
+    value.find(quote).map(|end| decode_entities(&value[..end]))
+}
+
+fn decode_entities(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let entity = rest
+            .find(';')
+            .filter(|&end| end <= 10)
+            .and_then(|end| Some((decode_entity(&rest[1..end])?, end)));
+
+        match entity {
+            Some((c, end)) => {
+                decoded.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    decoded.push_str(rest);
+    decoded
+}
+
+fn decode_entity(entity: &str) -> Option<char> {
+    match entity {
+        "lt" => Some('<'),
+        "gt" => Some('>'),
+        "amp" => Some('&'),
+        "quot" => Some('"'),
+        "apos" => Some('\''),
+        "nbsp" => Some(' '),
+        _ => {
+            let code = match entity
+                .strip_prefix("#x")
+                .or_else(|| entity.strip_prefix("#X"))
+            {
+                Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                None => entity.strip_prefix('#')?.parse().ok()?,
+            };
+            char::from_u32(code)
+        }
+    }
+}
+
+fn find_elements<'a>(nodes: &'a [Node], tag: &str) -> Vec<&'a Node> {
+    let mut found = vec![];
+
+    for node in nodes {
+        if let Node::Element { name, children, .. } = node {
+            if name == tag {
+                found.push(node);
+            } else {
+                found.extend(find_elements(children, tag));
+            }
+        }
+    }
+
+    found
+}
+
+fn render_blocks(nodes: &[Node], out: &mut String) {
+    for node in nodes {
+        let Node::Element { name, children, .. } = node else {
+            // whitespace between block elements is only formatting.
+            if matches!(node, Node::Text(text) if !text.trim().is_empty()) {
+                out.push_str(&render_inline(slice::from_ref(node)));
+            }
+            continue;
+        };
+
+        match name.as_str() {
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                let level = name[1..].parse::<usize>().unwrap_or(2);
+                out.push_str(&format!(
+                    "\n\n{} {}\n\n",
+                    "#".repeat(level),
+                    render_inline(children).trim()
+                ));
+            }
+            "p" => out.push_str(&format!("\n\n{}\n\n", render_inline(children).trim())),
+            "pre" => out.push_str(&format!(
+                "\n\n```\n{}\n```\n\n",
+                text_content(children).trim_end_matches('\n')
+            )),
+            "ul" | "ol" => {
+                out.push_str("\n\n");
+                let items = children.iter().filter_map(|child| match child {
+                    Node::Element { name, children, .. } if name == "li" => Some(children),
+                    _ => None,
+                });
+                for (i, item) in items.enumerate() {
+                    let marker = if name == "ol" {
+                        format!("{}.", i + 1)
+                    } else {
+                        "-".into()
+                    };
+                    out.push_str(&format!("{marker} {}\n", render_inline(item).trim()));
+                }
+                out.push_str("\n\n");
+            }
+            "script" | "style" | "head" | "nav" | "header" | "footer" => {}
+            _ => render_blocks(children, out),
+        }
+    }
+}
+
+fn render_inline(nodes: &[Node]) -> String {
+    let mut out = String::new();
+
+    for node in nodes {
+        match node {
+            Node::Text(text) => {
+                // whitespace in HTML collapses to a single space.
+                let mut was_space = out.ends_with(' ');
+                for c in text.chars() {
+                    let is_space = c.is_whitespace();
+                    if !(is_space && was_space) {
+                        out.push(if is_space { ' ' } else { c });
+                    }
+                    was_space = is_space;
+                }
+            }
+            Node::Element {
+                name,
+                href,
+                children,
+            } => match name.as_str() {
+                "em" | "i" => out.push_str(&format!("*{}*", render_inline(children))),
+                "strong" | "b" => out.push_str(&format!("**{}**", render_inline(children))),
+                "code" => match children.as_slice() {
+                    // an emphasized value like an expected answer, which markdown cannot emphasize within backticks.
+                    [Node::Element { name, children, .. }] if name == "em" => {
+                        out.push_str(&format!("*`{}`*", text_content(children)));
+                    }
+                    _ => out.push_str(&format!("`{}`", text_content(children))),
+                },
+                "a" => match href {
+                    Some(href) => out.push_str(&format!("[{}]({href})", render_inline(children))),
+                    None => out.push_str(&render_inline(children)),
+                },
+                "br" => out.push('\n'),
+                _ => out.push_str(&render_inline(children)),
+            },
+        }
+    }
+
+    out
+}
+
+fn text_content(nodes: &[Node]) -> String {
+    nodes
+        .iter()
+        .map(|node| match node {
+            Node::Text(text) => text.clone(),
+            Node::Element { children, .. } => text_content(children),
+        })
+        .collect()
+}
+
+/// Split the markdown of a puzzle description into the descriptions of its parts, at the heading of part two.
+#[must_use]
+pub fn split_parts(markdown: &str) -> Vec<&str> {
+    let part_two = markdown.match_indices('#').find_map(|(i, _)| {
+        let is_line_start = i == 0 || markdown[..i].ends_with('\n');
+        let line = markdown[i..].lines().next().unwrap_or_default();
+        (is_line_start && line.to_lowercase().contains("part two")).then_some(i)
+    });
+
+    match part_two {
+        Some(i) => vec![&markdown[..i], &markdown[i..]],
+        None => vec![markdown],
+    }
+}
+
+/// Render markdown as written by [`html_to_markdown`] for the terminal: headings and emphasis are bold, code is
+/// italic, code blocks are indented and links are followed by their target.
+#[must_use]
+pub fn to_ansi(markdown: &str) -> String {
+    let mut out = String::new();
+    let mut is_code_block = false;
+
+    for line in markdown.lines() {
+        if line.trim_start().starts_with("```") {
+            is_code_block = !is_code_block;
+            continue;
+        }
+
+        if is_code_block {
+            out.push_str(&format!("    {line}\n"));
+        } else if let Some(heading) = line.strip_prefix('#') {
+            let heading = heading.trim_start_matches('#').trim();
+            out.push_str(&format!(
+                "{ANSI_BOLD}{}{ANSI_RESET}\n",
+                inline_to_ansi(heading, ANSI_BOLD)
+            ));
+        } else {
+            out.push_str(&inline_to_ansi(line, ""));
+            out.push('\n');
+        }
+    }
+
+    out
+}
+
+/// Render the inline markdown of a line. `style` is restored after every styled span, as resets clear all styles.
+fn inline_to_ansi(line: &str, style: &str) -> String {
+    let mut out = String::new();
+    let mut rest = line;
+
+    // the content between `open` and the next `close`, along with the remaining text after it.
+    let span = |rest: &str, open: &str, close: &str| -> Option<(String, usize)> {
+        let inner = rest.strip_prefix(open)?;
+        let end = inner.find(close)?;
+        let content = &inner[..end];
+        (!content.is_empty() && !content.starts_with(' ') && !content.ends_with(' '))
+            .then(|| (content.to_string(), open.len() + end + close.len()))
+    };
+
+    while let Some(c) = rest.chars().next() {
+        let styled = if let Some((code, len)) = span(rest, "*`", "`*") {
+            Some((format!("{ANSI_BOLD}{ANSI_ITALIC}{code}"), len))
+        } else if let Some((text, len)) = span(rest, "**", "**").or_else(|| span(rest, "*", "*")) {
+            let text = inline_to_ansi(&text, &format!("{style}{ANSI_BOLD}"));
+            Some((format!("{ANSI_BOLD}{text}"), len))
+        } else if let Some((code, len)) = span(rest, "`", "`") {
+            Some((format!("{ANSI_ITALIC}{code}"), len))
+        } else if let Some((text, len)) = span(rest, "[", "](") {
+            span(&rest[len - 1..], "(", ")").map(|(href, href_len)| {
+                let text = inline_to_ansi(&text, style);
+                (format!("{text} {ANSI_ITALIC}({href})"), len - 1 + href_len)
+            })
+        } else {
+            None
+        };
+
+        match styled {
+            Some((styled, len)) => {
+                out.push_str(&format!("{styled}{ANSI_RESET}{style}"));
+                rest = &rest[len..];
+            }
+            None => {
+                out.push(c);
+                rest = &rest[c.len_utf8()..];
+            }
+        }
+    }
+
+    out
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{articles_to_markdown, html_to_markdown, split_parts, to_ansi};
+    use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+    #[test]
+    fn converts_puzzle() {
+        let html = r#"<html><body><main>
+<article class="day-desc"><h2>--- Day 1: Trebuchet?! ---</h2><p>Something is wrong with
+global snow production, and you've been selected to take a look. The Elves have even given you a
+<a href="/2023/map" target="_blank">map</a>; on it, they've used <em class="star">stars</em>.</p>
+<p>For example:</p>
+<pre><code>1abc2
+pqr3stu8vwx
+</code></pre>
+<ul>
+<li>In <code>1abc2</code>, the value is <code>12</code>.</li>
+<li>Adding these together produces <code><em>142</em></code>.</li>
+</ul>
+</article>
+<p>Your puzzle answer was <code>54390</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2><p>Is it &lt;= 3 &amp; &#x27;ok&#39;?</p></article>
+</main></body></html>"#;
+
+        assert_eq!(
+            articles_to_markdown(html).unwrap(),
+            "## --- Day 1: Trebuchet?! ---
+
+Something is wrong with global snow production, and you've been selected to take a look. The Elves have even given you a [map](/2023/map); on it, they've used *stars*.
+
+For example:
+
+```
+1abc2
+pqr3stu8vwx
+```
+
+- In `1abc2`, the value is `12`.
+- Adding these together produces *`142`*.
+
+## --- Part Two ---
+
+Is it <= 3 & 'ok'?"
+        );
+    }
+
+    #[test]
+    fn handles_missing_articles() {
+        assert_eq!(articles_to_markdown("<p>404 Not Found</p>"), None);
+        assert_eq!(html_to_markdown("<p>a<br/>b</p>"), "a\nb");
+        assert_eq!(html_to_markdown("a &unknown; b &amp"), "a &unknown; b &amp");
+    }
+
+    #[test]
+    fn splits_parts() {
+        let markdown = "## --- Day 1 ---\n\nText with a # sign.\n\n## --- Part Two ---\n\nMore.";
+        assert_eq!(
+            split_parts(markdown),
+            vec![
+                "## --- Day 1 ---\n\nText with a # sign.\n\n",
+                "## --- Part Two ---\n\nMore."
+            ]
+        );
+        assert_eq!(split_parts("## --- Day 1 ---"), vec!["## --- Day 1 ---"]);
+    }
+
+    #[test]
+    fn renders_for_terminal() {
+        let markdown = "## --- Day 1 ---
+
+A *star*, some `code`, the answer *`142`*, 2 * 3 * 4 and a [map](/2023/map).
+
+```
+1abc2
+```";
+
+        assert_eq!(
+            to_ansi(markdown),
+            format!(
+                "{ANSI_BOLD}--- Day 1 ---{ANSI_RESET}
+
+A {ANSI_BOLD}star{ANSI_RESET}, some {ANSI_ITALIC}code{ANSI_RESET}, the answer \
+{ANSI_BOLD}{ANSI_ITALIC}142{ANSI_RESET}, 2 * 3 * 4 and a map {ANSI_ITALIC}(/2023/map){ANSI_RESET}.
+
+    1abc2
+"
+            )
+        );
+    }
+}
@@ -0,0 +1,443 @@
+/// Client for the Advent of Code website, used to download inputs and puzzles and to submit answers.
+/// Requests are authenticated with the session cookie of a logged-in browser, see [`find_session`].
+use std::{
+    env,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use crate::{
+    template::{aoc_year, submissions::Verdict},
+    Day,
+};
+
+pub mod markdown;
+
+/// The website that is used unless `AOC_BASE_URL` is set, e.g. to a local mock server.
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// The environment variable that holds the session cookie, shared with aoc-cli.
+pub const SESSION_ENV: &str = "ADVENT_OF_CODE_SESSION";
+
+/// Set `AOC_CLIENT` to this value to call an installed aoc-cli instead of this client, see [`crate::template::aoc_cli`].
+pub const AOC_CLI: &str = "aoc-cli";
+
+/// The environment variable that holds the user agent of requests. The Advent of Code automation guidelines ask
+/// tools to identify themselves, ideally with a link to the repository and a way to contact its author.
+pub const USER_AGENT_ENV: &str = "AOC_USER_AGENT";
+
+/// Sent with every request unless [`USER_AGENT_ENV`] is set.
+pub const DEFAULT_USER_AGENT: &str =
+    concat!(env!("CARGO_PKG_NAME"), "/", env!("CARGO_PKG_VERSION"));
+
+#[derive(Debug)]
+pub enum ClientError {
+    /// Neither the environment variable nor one of the config files hold a session cookie.
+    MissingSession,
+    /// `AOC_YEAR` is not set to a year.
+    MissingYear,
+    /// The website did not accept the session cookie.
+    Unauthorized,
+    /// The puzzle does not exist, or it is not unlocked yet.
+    NotFound,
+    /// The website answered with an unexpected status.
+    Status(u16),
+    /// The website could not be reached.
+    Network(String),
+    /// The response does not contain a puzzle or a verdict.
+    UnexpectedResponse,
+    Io(io::Error),
+}
+
+impl Display for ClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ClientError::MissingSession => write!(
+                f,
+                "no session cookie found. Set {SESSION_ENV} or save it to \"~/.adventofcode.session\"."
+            ),
+            ClientError::MissingYear => write!(f, "AOC_YEAR is not set to a year."),
+            ClientError::Unauthorized => {
+                write!(f, "the session cookie was not accepted, it may have expired.")
+            }
+            ClientError::NotFound => write!(
+                f,
+                "the puzzle could not be found, it may not be unlocked yet."
+            ),
+            ClientError::Status(status) => {
+                write!(f, "the website answered with status {status}.")
+            }
+            ClientError::Network(e) => write!(f, "could not reach the website: {e}"),
+            ClientError::UnexpectedResponse => {
+                write!(f, "the response of the website could not be understood.")
+            }
+            ClientError::Io(e) => write!(f, "could not write output files to file system: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for ClientError {}
+
+impl From<io::Error> for ClientError {
+    fn from(e: io::Error) -> Self {
+        ClientError::Io(e)
+    }
+}
+
+/// The response to a submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Submission {
+    /// The verdict of the website, converted to markdown.
+    pub message: String,
+}
+
+impl Submission {
+    #[must_use]
+    pub fn is_correct(&self) -> bool {
+        Verdict::classify(&self.message) == Some(Verdict::Correct)
+    }
+}
+
+pub struct AocClient {
+    base_url: String,
+    session: String,
+    year: u16,
+    agent: ureq::Agent,
+}
+
+impl AocClient {
+    #[must_use]
+    pub fn new(base_url: &str, session: &str, year: u16, user_agent: &str) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').into(),
+            session: session.into(),
+            year,
+            agent: ureq::AgentBuilder::new()
+                .user_agent(user_agent)
+                .timeout(Duration::from_secs(30))
+                .redirects(0)
+                .build(),
+        }
+    }
+
+    /// Create a client for the year in `AOC_YEAR`, the session from [`find_session`], the website in
+    /// `AOC_BASE_URL`, which defaults to [`DEFAULT_BASE_URL`], and the user agent in [`USER_AGENT_ENV`], which defaults
+    /// to [`DEFAULT_USER_AGENT`].
+    pub fn from_env() -> Result<Self, ClientError> {
+        let year = aoc_year().ok_or(ClientError::MissingYear)?;
+        let session = find_session(env::var(SESSION_ENV).ok(), &session_files())
+            .ok_or(ClientError::MissingSession)?;
+        let base_url = env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+        let user_agent = env::var(USER_AGENT_ENV)
+            .ok()
+            .filter(|user_agent| !user_agent.trim().is_empty())
+            .unwrap_or_else(|| DEFAULT_USER_AGENT.into());
+
+        Ok(Self::new(&base_url, &session, year, &user_agent))
+    }
+
+    fn day_url(&self, day: Day) -> String {
+        format!("{}/{}/day/{}", self.base_url, self.year, day.into_inner())
+    }
+
+    /// Download the input of a day.
+    pub fn input(&self, day: Day) -> Result<String, ClientError> {
+        self.get(&format!("{}/input", self.day_url(day)))
+    }
+
+    /// Download the description of a day, converted to markdown. Contains part two once part one is solved.
+    pub fn puzzle(&self, day: Day) -> Result<String, ClientError> {
+        let html = self.get(&self.day_url(day))?;
+        markdown::articles_to_markdown(&html).ok_or(ClientError::UnexpectedResponse)
+    }
+
+    /// Submit the answer to a part and return the verdict of the website.
+    pub fn submit(&self, day: Day, part: u8, answer: &str) -> Result<Submission, ClientError> {
+        let response = self
+            .agent
+            .post(&format!("{}/answer", self.day_url(day)))
+            .set("Cookie", &format!("session={}", self.session))
+            .send_form(&[("level", &part.to_string()), ("answer", answer)]);
+
+        let html = read_response(response)?;
+        let message =
+            markdown::articles_to_markdown(&html).ok_or(ClientError::UnexpectedResponse)?;
+
+        Ok(Submission { message })
+    }
+
+    fn get(&self, url: &str) -> Result<String, ClientError> {
+        let response = self
+            .agent
+            .get(url)
+            .set("Cookie", &format!("session={}", self.session))
+            .call();
+
+        read_response(response)
+    }
+}
+
+fn read_response(response: Result<ureq::Response, ureq::Error>) -> Result<String, ClientError> {
+    match response {
+        // without a valid session, the website redirects to the login page.
+        Ok(response) if (300..400).contains(&response.status()) => Err(ClientError::Unauthorized),
+        Ok(response) => response
+            .into_string()
+            .map_err(|e| ClientError::Network(e.to_string())),
+        Err(ureq::Error::Status(404, _)) => Err(ClientError::NotFound),
+        // inputs differ by user, so asking for one without a valid session is a bad request.
+        Err(ureq::Error::Status(400 | 401 | 403, _)) => Err(ClientError::Unauthorized),
+        Err(ureq::Error::Status(status, response)) => {
+            let body = response.into_string().unwrap_or_default();
+            if body.contains("log in") {
+                Err(ClientError::Unauthorized)
+            } else {
+                Err(ClientError::Status(status))
+            }
+        }
+        Err(ureq::Error::Transport(e)) => Err(ClientError::Network(e.to_string())),
+    }
+}
+
+/// The path that the description of a day is cached at.
+#[must_use]
+pub fn get_puzzle_path(day: Day) -> String {
+    format!("data/puzzles/{day}.md")
+}
+
+/// Cache the description of a day, replacing an earlier one. The latest description contains part two once part
+/// one is solved.
+pub fn save_puzzle(day: Day, puzzle: &str) -> io::Result<()> {
+    let path = get_puzzle_path(day);
+    if let Some(dir) = Path::new(&path).parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    fs::write(path, format!("{puzzle}\n"))
+}
+
+/// Whether `AOC_CLIENT` asks to call aoc-cli rather than this client.
+#[must_use]
+pub fn uses_aoc_cli() -> bool {
+    env::var("AOC_CLIENT").is_ok_and(|client| client == AOC_CLI)
+}
+
+/// The files that a session cookie is read from, in order: `~/.adventofcode.session`, which aoc-cli uses as well,
+/// and `adventofcode.session` in the config directory.
+#[must_use]
+pub fn session_files() -> Vec<PathBuf> {
+    let home = env::var_os("HOME").map(PathBuf::from);
+    let config = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| home.as_ref().map(|home| home.join(".config")));
+
+    [
+        home.map(|home| home.join(".adventofcode.session")),
+        config.map(|config| config.join("adventofcode.session")),
+    ]
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+/// The session cookie from the environment variable [`SESSION_ENV`], or else from the first of `files` that
+/// contains one.
+#[must_use]
+pub fn find_session(from_env: Option<String>, files: &[PathBuf]) -> Option<String> {
+    from_env
+        .into_iter()
+        .chain(files.iter().filter_map(|file| read_session(file)))
+        .map(|session| session.trim().to_string())
+        .find(|session| !session.is_empty())
+}
+
+fn read_session(path: &Path) -> Option<String> {
+    fs::read_to_string(path).ok()
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{find_session, AocClient, ClientError, DEFAULT_USER_AGENT};
+    use crate::day;
+    use std::{
+        env, fs,
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        sync::mpsc,
+        thread,
+    };
+
+    /// A request received by [`MockServer`].
+    struct Request {
+        line: String,
+        headers: Vec<String>,
+        body: String,
+    }
+
+    /// Serves `responses` in order, one per connection, and reports the requests it received.
+    struct MockServer {
+        url: String,
+        requests: mpsc::Receiver<Request>,
+    }
+
+    impl MockServer {
+        fn start(responses: Vec<(u16, &'static str)>) -> Self {
+            let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+            let url = format!("http://{}", listener.local_addr().unwrap());
+            let (sender, requests) = mpsc::channel();
+
+            thread::spawn(move || {
+                for (status, body) in responses {
+                    let (stream, _) = listener.accept().unwrap();
+                    let mut reader = BufReader::new(stream);
+
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+
+                    let mut headers = vec![];
+                    loop {
+                        let mut header = String::new();
+                        reader.read_line(&mut header).unwrap();
+                        if header.trim().is_empty() {
+                            break;
+                        }
+                        headers.push(header.trim().to_string());
+                    }
+
+                    let length = headers
+                        .iter()
+                        .find_map(|header| {
+                            header
+                                .to_ascii_lowercase()
+                                .strip_prefix("content-length: ")
+                                .and_then(|length| length.parse().ok())
+                        })
+                        .unwrap_or(0);
+                    let mut request_body = vec![0; length];
+                    reader.read_exact(&mut request_body).unwrap();
+
+                    let mut stream = reader.into_inner();
+                    write!(
+                        stream,
+                        "HTTP/1.1 {status} Mock\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                        body.len()
+                    )
+                    .unwrap();
+
+                    let _ = sender.send(Request {
+                        line: line.trim().to_string(),
+                        headers,
+                        body: String::from_utf8(request_body).unwrap(),
+                    });
+                }
+            });
+
+            Self { url, requests }
+        }
+
+        fn next_request(&self) -> Request {
+            self.requests.recv().unwrap()
+        }
+    }
+
+    #[test]
+    fn downloads_input() {
+        let server = MockServer::start(vec![(200, "1abc2\n")]);
+        let client = AocClient::new(
+            &server.url,
+            "secret",
+            2023,
+            "github.com/me/aoc by me@example.com",
+        );
+
+        assert_eq!(client.input(day!(1)).unwrap(), "1abc2\n");
+
+        let request = server.next_request();
+        assert_eq!(request.line, "GET /2023/day/1/input HTTP/1.1");
+        assert!(request
+            .headers
+            .contains(&"Cookie: session=secret".to_string()));
+        assert!(request
+            .headers
+            .contains(&"User-Agent: github.com/me/aoc by me@example.com".to_string()));
+    }
+
+    #[test]
+    fn downloads_puzzle_as_markdown() {
+        let server = MockServer::start(vec![(
+            200,
+            "<main><article class=\"day-desc\"><h2>--- Day 5 ---</h2><p>Seeds.</p></article></main>",
+        )]);
+        let client = AocClient::new(&server.url, "secret", 2023, DEFAULT_USER_AGENT);
+
+        assert_eq!(
+            client.puzzle(day!(5)).unwrap(),
+            "## --- Day 5 ---\n\nSeeds."
+        );
+        assert_eq!(server.next_request().line, "GET /2023/day/5 HTTP/1.1");
+    }
+
+    #[test]
+    fn submits_answer() {
+        let server = MockServer::start(vec![(
+            200,
+            "<main><article><p>That's the right answer! You are <em>one gold star</em> closer.</p></article></main>",
+        )]);
+        let client = AocClient::new(&server.url, "secret", 2023, DEFAULT_USER_AGENT);
+
+        let submission = client.submit(day!(2), 1, "2683").unwrap();
+        assert!(submission.is_correct());
+        assert_eq!(
+            submission.message,
+            "That's the right answer! You are *one gold star* closer."
+        );
+
+        let request = server.next_request();
+        assert_eq!(request.line, "POST /2023/day/2/answer HTTP/1.1");
+        assert_eq!(request.body, "level=1&answer=2683");
+    }
+
+    #[test]
+    fn reports_errors() {
+        let server = MockServer::start(vec![
+            (404, "404 Not Found"),
+            (
+                400,
+                "Puzzle inputs differ by user.  Please log in to get your puzzle input.",
+            ),
+            (500, "Internal Server Error"),
+        ]);
+        let client = AocClient::new(&server.url, "expired", 2023, DEFAULT_USER_AGENT);
+
+        assert!(matches!(client.input(day!(25)), Err(ClientError::NotFound)));
+        assert!(matches!(
+            client.input(day!(1)),
+            Err(ClientError::Unauthorized)
+        ));
+        assert!(matches!(
+            client.input(day!(1)),
+            Err(ClientError::Status(500))
+        ));
+    }
+
+    #[test]
+    fn finds_session() {
+        let dir = env::temp_dir().join(format!("aoc-session-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let (empty, file) = (dir.join("empty"), dir.join("session"));
+        fs::write(&empty, "\n").unwrap();
+        fs::write(&file, "from-file\n").unwrap();
+
+        let files = [dir.join("missing"), empty, file];
+        assert_eq!(
+            find_session(Some("from-env".into()), &files).as_deref(),
+            Some("from-env")
+        );
+        assert_eq!(find_session(None, &files).as_deref(), Some("from-file"));
+        assert_eq!(find_session(None, &files[..2]), None);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,164 @@
+/// Module that saves the benchmarks of a run as a named baseline and compares later runs with it.
+/// Baselines are stored in `target/baselines/<name>.jsonl`, in the same format as the benchmark history.
+use std::{
+    env, fs,
+    io::{self, Write},
+    path::PathBuf,
+};
+
+use crate::template::{
+    bench_history::Record,
+    stats::{self, BenchStats},
+    ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+};
+use crate::Day;
+
+/// Changes of the mean below this many percent are reported as unchanged, even if they are significant.
+/// With thousands of samples, even the drift of a busy machine is significant.
+pub const NOISE_THRESHOLD: f64 = 2.0;
+
+#[must_use]
+pub fn get_path(name: &str) -> PathBuf {
+    let target_dir = env::var("CARGO_TARGET_DIR").unwrap_or_else(|_| "target".into());
+    PathBuf::from(target_dir)
+        .join("baselines")
+        .join(format!("{name}.jsonl"))
+}
+
+/// Save `records` as the baseline `name`, replacing a previous baseline of the same name.
+pub fn save(name: &str, records: &[Record]) -> io::Result<()> {
+    let path = get_path(name);
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    let mut file = fs::File::create(path)?;
+
+    for record in records {
+        writeln!(file, "{}", serde_json::to_string(record)?)?;
+    }
+
+    Ok(())
+}
+
+/// Load the baseline `name`.
+pub fn load(name: &str) -> io::Result<Vec<Record>> {
+    let contents = fs::read_to_string(get_path(name))?;
+
+    Ok(contents
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+/// How the time of a part changed compared with a baseline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Faster,
+    Slower,
+    /// The difference is within the noise of the samples, or below [`NOISE_THRESHOLD`].
+    Unchanged,
+}
+
+/// A part of the current run next to the same part in a baseline.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Comparison {
+    pub day: Day,
+    pub part: u8,
+    /// Statistics and sample count of the baseline, `None` if the baseline did not benchmark the part.
+    pub old: Option<(BenchStats, u128)>,
+    pub new: (BenchStats, u128),
+}
+
+impl Comparison {
+    /// The change of the mean in percent.
+    #[must_use]
+    pub fn change(&self) -> Option<f64> {
+        let old = self.old?.0.mean.as_secs_f64();
+        let new = self.new.0.mean.as_secs_f64();
+
+        (old > 0.0).then(|| (new - old) / old * 100.0)
+    }
+
+    #[must_use]
+    pub fn verdict(&self) -> Option<Verdict> {
+        let (old, old_samples) = self.old?;
+        let (new, new_samples) = self.new;
+        let is_noise = self.change()?.abs() < NOISE_THRESHOLD;
+
+        Some(
+            if is_noise || !stats::is_significant(&old, old_samples, &new, new_samples) {
+                Verdict::Unchanged
+            } else if new.mean < old.mean {
+                Verdict::Faster
+            } else {
+                Verdict::Slower
+            },
+        )
+    }
+
+    /// The old and the new mean with the change between them, e.g. `1.2s → 3.4ms (-99.7%, faster)`.
+    #[must_use]
+    pub fn summary(&self) -> String {
+        let new = self.new.0.mean;
+
+        match (self.old, self.change(), self.verdict()) {
+            (Some((old, _)), Some(change), Some(verdict)) => {
+                let verdict = match verdict {
+                    Verdict::Faster => format!("{ANSI_BOLD}faster{ANSI_RESET}"),
+                    Verdict::Slower => format!("{ANSI_BOLD}slower{ANSI_RESET}"),
+                    Verdict::Unchanged => format!("{ANSI_ITALIC}no significant change{ANSI_RESET}"),
+                };
+                format!("{:.1?} → {new:.1?} ({change:+.1}%, {verdict})", old.mean)
+            }
+            _ => format!("{new:.1?} ({ANSI_ITALIC}not in baseline{ANSI_RESET})"),
+        }
+    }
+}
+
+/// Compare every part of the current run with the same part of the baseline.
+#[must_use]
+pub fn compare(baseline: &[Record], current: &[Record]) -> Vec<Comparison> {
+    current
+        .iter()
+        .map(|new| Comparison {
+            day: new.day,
+            part: new.part,
+            old: baseline
+                .iter()
+                .find(|old| old.day == new.day && old.part == new.part)
+                .map(|old| (old.stats, old.samples)),
+            new: (new.stats, new.samples),
+        })
+        .collect()
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{compare, Verdict};
+    use crate::template::bench_history::Record;
+    use std::time::Duration;
+
+    fn record(part: u8, mean_nanos: u64) -> Record {
+        Record::sampled(
+            part,
+            Duration::from_nanos(mean_nanos),
+            Duration::from_nanos(mean_nanos / 20),
+        )
+    }
+
+    #[test]
+    fn compares_parts_with_baseline() {
+        let baseline = [record(1, 1000), record(2, 1_000_000)];
+        let current = [record(1, 1015), record(2, 10_000), record(0, 500)];
+
+        let comparisons = compare(&baseline, &current);
+        assert_eq!(comparisons[0].verdict(), Some(Verdict::Unchanged));
+        assert_eq!(comparisons[1].verdict(), Some(Verdict::Faster));
+        assert_eq!(comparisons[1].change(), Some(-99.0));
+        // the parse phase is new and has nothing to compare with.
+        assert_eq!(comparisons[2].verdict(), None);
+        assert_eq!(comparisons[2].change(), None);
+    }
+}
@@ -0,0 +1,334 @@
+/// Module that keeps a history of benchmark results in `data/benchmarks.jsonl`.
+/// Every timed release run of `all` appends one JSON record per part, so that timings can be compared across commits.
+use std::{
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    process::Command,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::template::{
+    runner::{PartResult, PARSE_PART},
+    stats::BenchStats,
+};
+use crate::Day;
+
+pub const HISTORY_PATH: &str = "data/benchmarks.jsonl";
+
+/// Regressions are changes of the mean beyond this many percent, unless `--threshold` says otherwise.
+pub const DEFAULT_THRESHOLD: f64 = 10.0;
+
+/// The benchmark of one part in one run.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Record {
+    /// Short hash of the commit that was checked out, if the repository is a git repository.
+    pub commit: Option<String>,
+    /// Whether the working tree had uncommitted changes.
+    #[serde(default)]
+    pub dirty: bool,
+    /// Seconds since the unix epoch. All records of a run share the same timestamp.
+    pub timestamp: u64,
+    pub day: Day,
+    pub part: u8,
+    pub samples: u128,
+    pub stats: BenchStats,
+}
+
+impl Record {
+    /// The commit of the record as shown in messages, e.g. `a1b2c3d*` for a dirty working tree.
+    #[must_use]
+    pub fn label(&self) -> String {
+        let commit = self.commit.as_deref().unwrap_or("unknown");
+        if self.dirty {
+            format!("{commit}*")
+        } else {
+            commit.into()
+        }
+    }
+}
+
+/// Create the records of a run from the results of its parts.
+/// Only benchmarked parts on the default input are recorded, as other inputs are not comparable across runs.
+#[must_use]
+pub fn records_of(results: &[PartResult]) -> Vec<Record> {
+    let (commit, dirty) = git_revision();
+    let timestamp = timestamp();
+
+    results
+        .iter()
+        .filter(|result| result.input.is_none() && result.status.has_timing())
+        .filter_map(|result| {
+            Some(Record {
+                commit: commit.clone(),
+                dirty,
+                timestamp,
+                day: result.day,
+                part: result.part,
+                samples: result.samples,
+                stats: result.stats?,
+            })
+        })
+        .collect()
+}
+
+/// Append records to the history file, creating it if necessary.
+pub fn append(records: &[Record]) -> io::Result<()> {
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(HISTORY_PATH)?;
+
+    for record in records {
+        writeln!(file, "{}", serde_json::to_string(record)?)?;
+    }
+
+    Ok(())
+}
+
+/// Read all records of the history file, oldest first. Lines that cannot be parsed are skipped.
+pub fn read() -> io::Result<Vec<Record>> {
+    match fs::read_to_string(HISTORY_PATH) {
+        Ok(contents) => Ok(parse(&contents)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(vec![]),
+        Err(e) => Err(e),
+    }
+}
+
+fn parse(contents: &str) -> Vec<Record> {
+    contents
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect()
+}
+
+/// Seconds since the unix epoch.
+pub(crate) fn timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |x| x.as_secs())
+}
+
+fn git_revision() -> (Option<String>, bool) {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+
+    let commit = git(&["rev-parse", "--short", "HEAD"]);
+    let dirty = commit.is_some() && git(&["status", "--porcelain"]).is_some_and(|x| !x.is_empty());
+
+    (commit, dirty)
+}
+
+/// The development of one part's mean over the recorded runs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Trend {
+    pub day: Day,
+    pub part: u8,
+    /// Means of all recorded runs, oldest first.
+    pub means: Vec<Duration>,
+    pub latest: Record,
+    /// The record that the latest one is compared with.
+    pub baseline: Option<Record>,
+}
+
+impl Trend {
+    /// The change of the mean from the baseline to the latest run, in percent.
+    #[must_use]
+    pub fn change(&self) -> Option<f64> {
+        let baseline = self.baseline.as_ref()?.stats.mean.as_secs_f64();
+        let latest = self.latest.stats.mean.as_secs_f64();
+
+        (baseline > 0.0).then(|| (latest - baseline) / baseline * 100.0)
+    }
+
+    /// Whether the latest run is slower than the baseline by more than `threshold` percent.
+    #[must_use]
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.change().is_some_and(|change| change > threshold)
+    }
+
+    /// The name of the part as shown in the output.
+    #[must_use]
+    pub fn label(&self) -> String {
+        if self.part == PARSE_PART {
+            "Parse".into()
+        } else {
+            format!("Part {}", self.part)
+        }
+    }
+}
+
+/// Group records by day and part, ordered by day and part.
+/// The latest record of every part is compared with the latest earlier record of the `baseline` commit, if given, or
+/// with the record before it otherwise. Commits are matched by prefix.
+#[must_use]
+pub fn trends(records: &[Record], baseline: Option<&str>) -> Vec<Trend> {
+    let mut keys: Vec<(Day, u8)> = records.iter().map(|x| (x.day, x.part)).collect();
+    keys.sort_unstable();
+    keys.dedup();
+
+    keys.into_iter()
+        .filter_map(|(day, part)| {
+            let history: Vec<&Record> = records
+                .iter()
+                .filter(|x| x.day == day && x.part == part)
+                .collect();
+
+            let (latest, previous) = history.split_last()?;
+
+            let baseline = match baseline {
+                // the latest record itself is never its baseline, even if it ran on the baseline commit.
+                Some(commit) => previous
+                    .iter()
+                    .rev()
+                    .find(|x| x.commit.as_deref().is_some_and(|c| c.starts_with(commit))),
+                None => previous.last(),
+            };
+
+            Some(Trend {
+                day,
+                part,
+                means: history.iter().map(|x| x.stats.mean).collect(),
+                latest: (*latest).clone(),
+                baseline: baseline.map(|x| (*x).clone()),
+            })
+        })
+        .collect()
+}
+
+/// A sparkline of `values`, one bar per value, scaled between their minimum and maximum.
+#[must_use]
+pub fn sparkline(values: &[Duration]) -> String {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+    let (Some(min), Some(max)) = (values.iter().min(), values.iter().max()) else {
+        return String::new();
+    };
+
+    let range = (*max - *min).as_secs_f64();
+
+    values
+        .iter()
+        .map(|x| {
+            if range == 0.0 {
+                return BARS[0];
+            }
+
+            let scaled = (*x - *min).as_secs_f64() / range * (BARS.len() - 1) as f64;
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            BARS[scaled.round() as usize]
+        })
+        .collect()
+}
+
+#[cfg(feature = "test_lib")]
+impl Record {
+    /// A record of `part` of day 1 for tests, over 1000 samples that all took `mean` apart from a spread of `std_dev`.
+    pub(crate) fn sampled(part: u8, mean: Duration, std_dev: Duration) -> Self {
+        Self {
+            commit: None,
+            dirty: false,
+            timestamp: 0,
+            day: crate::day!(1),
+            part,
+            samples: 1000,
+            stats: BenchStats {
+                mean,
+                median: mean,
+                std_dev,
+                min: mean,
+                max: mean,
+                p95: mean,
+                p99: mean,
+                outliers: 0,
+            },
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse, sparkline, trends, Record};
+    use std::time::Duration;
+
+    fn record(commit: &str, part: u8, mean_micros: u64) -> Record {
+        let mean = Duration::from_micros(mean_micros);
+        Record {
+            commit: Some(commit.into()),
+            ..Record::sampled(part, mean, Duration::ZERO)
+        }
+    }
+
+    #[test]
+    fn round_trips_records() {
+        let record = record("a1b2c3d", 1, 120);
+        let line = serde_json::to_string(&record).unwrap();
+        assert!(line.contains("\"stats\":{\"mean_nanos\":120000"));
+        assert_eq!(parse(&format!("{line}\nnot json\n")), vec![record]);
+    }
+
+    #[test]
+    fn compares_with_previous_run() {
+        let records = [
+            record("aaaaaaa", 1, 100),
+            record("aaaaaaa", 2, 100),
+            record("bbbbbbb", 1, 120),
+            record("ccccccc", 1, 105),
+        ];
+
+        let trends = trends(&records, None);
+        assert_eq!(trends.len(), 2);
+
+        let part_one = &trends[0];
+        assert_eq!(part_one.means.len(), 3);
+        assert_eq!(part_one.baseline.as_ref().unwrap().label(), "bbbbbbb");
+        assert!(part_one.change().unwrap() < -12.0);
+        assert!(!part_one.is_regression(10.0));
+
+        // a part that was only recorded once has nothing to compare with.
+        assert_eq!(trends[1].change(), None);
+    }
+
+    #[test]
+    fn compares_with_baseline_commit() {
+        let records = [
+            record("aaaaaaa", 1, 100),
+            record("bbbbbbb", 1, 90),
+            record("ccccccc", 1, 115),
+        ];
+
+        let trend = &trends(&records, Some("aaa"))[0];
+        assert_eq!(trend.baseline.as_ref().unwrap().label(), "aaaaaaa");
+        assert!(trend.is_regression(10.0));
+        assert!(!trend.is_regression(20.0));
+    }
+
+    #[test]
+    fn compares_rerun_with_earlier_run_of_baseline_commit() {
+        let records = [record("aaaaaaa", 1, 100), record("aaaaaaa", 1, 130)];
+
+        let trend = &trends(&records, Some("aaaaaaa"))[0];
+        assert_eq!(trend.baseline.as_ref().unwrap().stats.mean.as_micros(), 100);
+        assert!(trend.is_regression(10.0));
+
+        // a commit that only the latest run was recorded on has nothing to compare with.
+        let trend = &trends(&records[1..], Some("aaaaaaa"))[0];
+        assert!(trend.baseline.is_none());
+        assert!(trends(&records, Some("fff"))[0].baseline.is_none());
+    }
+
+    #[test]
+    fn draws_sparkline() {
+        let values = [100, 200, 800].map(Duration::from_nanos);
+        assert_eq!(sparkline(&values), "▁▂█");
+        assert_eq!(sparkline(&[Duration::ZERO; 2]), "▁▁");
+        assert_eq!(sparkline(&[]), "");
+    }
+}
@@ -1,46 +1,428 @@
-use std::io;
+use std::collections::BTreeMap;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::{fmt::Display, io, thread};
 
 use crate::template::{
-    readme_benchmarks::{self, Timings},
+    baseline::{self, Comparison},
+    bench_history,
+    commands::RunOptions,
+    exit_code::ExitCode,
+    inputs::InputSelection,
+    readme_benchmarks::{self, PartTimings, Timings},
+    registry::{self, Solution},
+    runner::{
+        self, print_part_result, OutputFormat, PartResult, PartStatus, SolutionRun, PARSE_PART,
+    },
     ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
 };
 use crate::{all_days, Day};
 
-pub fn handle(is_release: bool, is_timed: bool) {
-    let mut timings: Vec<Timings> = vec![];
+/// Names of baselines to save the benchmarks of a timed run as, or to compare them with, see [`baseline`].
+#[derive(Debug, Clone, Default)]
+pub struct Baselines {
+    pub save: Option<String>,
+    pub compare: Option<String>,
+}
 
-    all_days().for_each(|day| {
-        if day > 1 {
-            println!();
-        }
+pub fn handle(
+    solutions: &[Solution],
+    is_answers_required: bool,
+    jobs: usize,
+    options: &RunOptions,
+    baselines: &Baselines,
+) {
+    let (is_release, is_timed) = (options.release, options.time);
+    let is_text = options.format == OutputFormat::Text;
+
+    if !is_timed && (baselines.save.is_some() || baselines.compare.is_some()) {
+        eprintln!("--save-baseline and --baseline require --time.");
+        ExitCode::Error.exit();
+    }
 
-        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-        println!("------");
+    // fail before running anything if the baseline does not exist.
+    let compare_with = baselines
+        .compare
+        .as_ref()
+        .map(|name| match baseline::load(name) {
+            Ok(records) => (name, records),
+            Err(e) => {
+                eprintln!(
+                    "could not read baseline \"{name}\" from \"{}\": {e}",
+                    baseline::get_path(name).display()
+                );
+                ExitCode::Error.exit();
+            }
+        });
+    let mut timings: Vec<Timings> = vec![];
+    let mut benchmarked: Vec<PartResult> = vec![];
+    let mut exit_code = ExitCode::Success;
+    let mut mismatches = 0;
+    let mut unverified = 0;
+    let mut failures = 0;
+
+    let mut finish_day = |day: Day, run: &Result<SolutionRun, Error>| {
+        let results = match run {
+            Ok(run) => {
+                exit_code = exit_code.max(run.exit_code());
+                &run.results
+            }
+            Err(e) => {
+                if is_text {
+                    println!("{e}");
+                } else {
+                    eprintln!("Day {day}: {e}");
+                }
+                exit_code = exit_code.max(e.exit_code());
+                return;
+            }
+        };
 
-        let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+        mismatches += results
+            .iter()
+            .filter(|r| r.status == PartStatus::Wrong)
+            .count();
+        unverified += results
+            .iter()
+            .filter(|r| r.input.is_none() && r.status == PartStatus::Solved && r.expected.is_none())
+            .count();
+        failures += results.iter().filter(|r| r.status.is_failure()).count();
 
-        if output.is_empty() {
-            println!("Not solved.");
+        if results.is_empty() {
+            if is_text {
+                println!("Not solved.");
+            }
         } else {
-            let val = child_commands::parse_exec_time(&output, day);
-            timings.push(val);
+            timings.push(collect_timings(day, results, is_timed));
+
+            if is_timed {
+                benchmarked.extend_from_slice(results);
+            }
         }
-    });
+    };
+
+    // parallel runs would skew each others timings and allocation counts.
+    if jobs > 1 && (is_timed || options.memory) {
+        eprintln!("Running days one at a time, as timings and memory usage are measured.");
+    }
+
+    if jobs > 1 && !is_timed && !options.memory {
+        run_parallel(jobs, options, |day, outputs, results| {
+            print_day_header(day, is_text);
+            let mut printer = Printer::new(options.format);
+            outputs.iter().for_each(|output| printer.print(output));
+            finish_day(day, results);
+        });
+    } else {
+        let mut registered = solutions;
+
+        all_days().for_each(|day| {
+            print_day_header(day, is_text);
+            let mut printer = Printer::new(options.format);
+            let run = run_day(registered, day, options, |output| printer.print(&output));
+
+            // a part that timed out cannot be stopped, and would slow down the remaining days if they ran in this
+            // process. Child processes are killed along with their parts once they exit.
+            if !registered.is_empty() && has_timed_out(&run) {
+                eprintln!(
+                    "Running the remaining days in separate processes, as day {day} timed out."
+                );
+                registered = &[];
+            }
+
+            finish_day(day, &run);
+        });
+    }
+
+    if failures > 0 {
+        eprintln!("\n{failures} part(s) failed.");
+    }
+
+    if mismatches > 0 {
+        eprintln!("\n{mismatches} answer(s) did not match the answers stored in data/answers.");
+    }
+
+    if is_answers_required && unverified > 0 {
+        eprintln!("\n{unverified} answer(s) could not be verified, as data/answers does not contain them.");
+        exit_code = exit_code.max(ExitCode::WrongAnswer);
+    }
 
     if is_timed {
         let total_millis = timings.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64;
 
-        println!("\n{ANSI_BOLD}Total:{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}");
+        if is_text {
+            println!(
+                "\n{ANSI_BOLD}Total:{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
+            );
+        }
+
+        let records = bench_history::records_of(&benchmarked);
+
+        if let Some((name, baseline_records)) = &compare_with {
+            print_comparisons(
+                name,
+                &baseline::compare(baseline_records, &records),
+                is_text,
+            );
+        }
+
+        if let Some(name) = &baselines.save {
+            match baseline::save(name, &records) {
+                Ok(()) => eprintln!("Saved benchmarks as baseline \"{name}\"."),
+                Err(e) => eprintln!("Failed to save baseline \"{name}\": {e}"),
+            }
+        }
 
         if is_release {
             match readme_benchmarks::update(timings, total_millis) {
-                Ok(()) => println!("Successfully updated README with benchmarks."),
+                Ok(()) if is_text => println!("Successfully updated README with benchmarks."),
+                Ok(()) => eprintln!("Successfully updated README with benchmarks."),
                 Err(_) => {
                     eprintln!("Failed to update readme with benchmarks.");
                 }
             }
+
+            match bench_history::append(&records) {
+                Ok(()) if is_text => println!(
+                    "Recorded benchmarks in \"{}\".",
+                    bench_history::HISTORY_PATH
+                ),
+                Ok(()) => {}
+                Err(e) => eprintln!("Failed to record benchmark history: {e}"),
+            }
         }
     }
+
+    if exit_code != ExitCode::Success {
+        exit_code.exit();
+    }
+}
+
+/// Print the time of every part next to its time in the baseline `name`.
+fn print_comparisons(name: &str, comparisons: &[Comparison], is_text: bool) {
+    let mut lines = vec![format!(
+        "\n{ANSI_BOLD}Compared with baseline \"{name}\":{ANSI_RESET}"
+    )];
+
+    for comparison in comparisons {
+        let part = match comparison.part {
+            PARSE_PART => "Parse".to_string(),
+            part => format!("Part {part}"),
+        };
+
+        lines.push(format!(
+            "Day {} {part}: {}",
+            comparison.day,
+            comparison.summary()
+        ));
+    }
+
+    for line in lines {
+        if is_text {
+            println!("{line}");
+        } else {
+            eprintln!("{line}");
+        }
+    }
+}
+
+fn print_day_header(day: Day, is_text: bool) {
+    if is_text {
+        if day > 1 {
+            println!();
+        }
+
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        println!("------");
+    }
+}
+
+/// Run a single day, passing everything that has to be printed to `on_output`.
+/// Registered solutions print their results themselves.
+fn run_day(
+    solutions: &[Solution],
+    day: Day,
+    options: &RunOptions,
+    on_output: impl FnMut(Output),
+) -> Result<SolutionRun, Error> {
+    // memory and spans can only be measured in-process if this binary was built with their features.
+    let in_process = (!options.memory || cfg!(feature = "memory"))
+        && (!options.spans || cfg!(feature = "spans"));
+
+    match registry::find(solutions, day) {
+        Some(solution) if in_process => run_in_process(solution, &options.inputs),
+        // fall back to running the solution binary for days that are not part of the registry.
+        _ => child_commands::run_solution(day, options, on_output),
+    }
+}
+
+/// Run the solution binary of a day, printing its results like `all` does.
+/// Errors are printed as well, and yield no results.
+pub(crate) fn run_binary(day: Day, options: &RunOptions) -> Vec<PartResult> {
+    let mut printer = Printer::new(options.format);
+
+    match child_commands::run_solution(day, options, |output| printer.print(&output)) {
+        Ok(run) => run.results,
+        Err(e) => {
+            eprintln!("{e}");
+            vec![]
+        }
+    }
+}
+
+pub(crate) use child_commands::build_solution;
+
+/// Run days on `jobs` threads. `on_day` is called with the buffered output of every day, in day order.
+/// Every day runs in a child process, as output that solutions print themselves can only be captured per process.
+fn run_parallel(
+    jobs: usize,
+    options: &RunOptions,
+    mut on_day: impl FnMut(Day, &[Output], &Result<SolutionRun, Error>),
+) {
+    let days: Vec<Day> = all_days().collect();
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(days.len()) {
+            let sender = sender.clone();
+            let (days, next) = (&days, &next);
+
+            scope.spawn(move || loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(&day) = days.get(index) else {
+                    break;
+                };
+
+                let mut outputs = vec![];
+                let run = child_commands::run_solution(day, options, |output| outputs.push(output));
+                let _ = sender.send((index, outputs, run));
+            });
+        }
+
+        drop(sender);
+
+        // days finish out of order, so hold on to them until all previous days have been printed.
+        let mut finished = BTreeMap::new();
+        let mut printed = 0;
+
+        for (index, outputs, run) in receiver {
+            finished.insert(index, (outputs, run));
+
+            while let Some((outputs, run)) = finished.remove(&printed) {
+                on_day(days[printed], &outputs, &run);
+                printed += 1;
+            }
+        }
+    });
+}
+
+/// Whether a part of a run timed out, see [`PartStatus::TimedOut`].
+fn has_timed_out(run: &Result<SolutionRun, Error>) -> bool {
+    run.as_ref().is_ok_and(|run| {
+        run.results
+            .iter()
+            .any(|result| result.status == PartStatus::TimedOut)
+    })
+}
+
+/// Run a registered solution in the current process.
+/// Missing inputs are reported in the failure of the run. Panics outside of parts are caught, so that the remaining
+/// days still run.
+fn run_in_process(solution: &Solution, inputs: &InputSelection) -> Result<SolutionRun, Error> {
+    panic::catch_unwind(AssertUnwindSafe(|| runner::run_inputs(solution, inputs))).map_err(
+        |payload| Error::Crashed(format!("panicked: {}", runner::panic_message(&*payload))),
+    )
+}
+
+/// Something that a day printed.
+#[derive(Debug, Clone)]
+enum Output {
+    Record(Box<PartResult>),
+    /// A line that a solution binary printed itself.
+    Line(String),
+    /// A line that a solution binary printed to stderr.
+    ErrorLine(String),
+}
+
+/// Prints the output of a day, labeling results of alternate inputs.
+struct Printer {
+    format: OutputFormat,
+    last_input: Option<Option<String>>,
+}
+
+impl Printer {
+    fn new(format: OutputFormat) -> Self {
+        Self {
+            format,
+            last_input: None,
+        }
+    }
+
+    fn print(&mut self, output: &Output) {
+        match output {
+            Output::Record(result) => {
+                if let (Some(name), OutputFormat::Text) = (&result.input, self.format) {
+                    if self.last_input.as_ref() != Some(&result.input) {
+                        println!("{ANSI_ITALIC}Input: {name}{ANSI_RESET}");
+                    }
+                }
+
+                self.last_input = Some(result.input.clone());
+                print_part_result(result, self.format);
+            }
+            Output::Line(line) if self.format == OutputFormat::Text => println!("{line}"),
+            Output::Line(line) | Output::ErrorLine(line) => eprintln!("{line}"),
+        }
+    }
+}
+
+/// Collect the README timings of a day from the records of its parts.
+fn collect_timings(day: Day, results: &[PartResult], is_timed: bool) -> Timings {
+    let mut timings = Timings {
+        day,
+        parse: None,
+        part_1: None,
+        part_2: None,
+        total_nanos: 0_f64,
+    };
+
+    if !is_timed {
+        return timings;
+    }
+
+    // only the default input is representative for the README.
+    for result in results.iter().filter(|r| r.input.is_none()) {
+        let part_timings = if result.status.is_failure() {
+            PartTimings::failed()
+        } else if result.status.has_timing() {
+            PartTimings {
+                mean: format!("{:.1?}", result.duration),
+                stats: result.stats,
+                memory: result.memory,
+                failed: false,
+            }
+        } else {
+            continue;
+        };
+
+        match result.part {
+            PARSE_PART => timings.parse = Some(part_timings),
+            1 => timings.part_1 = Some(part_timings),
+            2 => timings.part_2 = Some(part_timings),
+            _ => {}
+        }
+
+        if result.status.has_timing() {
+            #[allow(clippy::cast_precision_loss)]
+            {
+                timings.total_nanos += result.duration.as_nanos() as f64;
+            }
+        }
+    }
+
+    timings
 }
 
 #[derive(Debug)]
@@ -48,6 +430,38 @@ pub enum Error {
     BrokenPipe,
     Parser(String),
     IO(io::Error),
+    /// The day has no solution file yet.
+    NotScaffolded,
+    /// The solution binary did not compile.
+    BuildFailed,
+    /// The solution exited without reporting its results, e.g. because it panicked outside of a part.
+    Crashed(String),
+}
+
+impl Error {
+    /// How this error affects the exit code of `all`. Days that were not scaffolded yet are not a failure.
+    #[must_use]
+    pub fn exit_code(&self) -> ExitCode {
+        match self {
+            Error::NotScaffolded => ExitCode::Success,
+            Error::BuildFailed => ExitCode::BuildFailed,
+            Error::Crashed(_) => ExitCode::RuntimeFailure,
+            Error::BrokenPipe | Error::Parser(_) | Error::IO(_) => ExitCode::Error,
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::BrokenPipe => write!(f, "Could not read the output of the solution."),
+            Error::Parser(e) => write!(f, "Could not parse the output of the solution: {e}"),
+            Error::IO(e) => write!(f, "Could not run the solution: {e}"),
+            Error::NotScaffolded => write!(f, "Not scaffolded."),
+            Error::BuildFailed => write!(f, "Build failed."),
+            Error::Crashed(reason) => write!(f, "Runtime failure: the solution {reason}"),
+        }
+    }
 }
 
 impl From<std::io::Error> for Error {
@@ -62,39 +476,65 @@ pub fn get_path_for_bin(day: Day) -> String {
 }
 
 /// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
+/// This module encapsulates interaction with these binaries, both invoking them as well as reading the JSON records they print.
 mod child_commands {
-    use super::{get_path_for_bin, Error};
+    use super::{get_path_for_bin, Error, Output};
+    use crate::template::commands::RunOptions;
+    use crate::template::exit_code::ExitCode;
+    use crate::template::runner::{PartResult, SolutionRun};
     use crate::Day;
     use std::{
         io::{BufRead, BufReader},
         path::Path,
         process::{Command, Stdio},
+        sync::mpsc,
         thread,
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
-        // skip command invocation for days that have not been scaffolded yet.
+    /// Compile the solution bin for a given day. Compiler messages are printed to stderr.
+    /// Building separately from running tells compile errors apart from failures of the solution itself.
+    pub fn build_solution(day: Day, options: &RunOptions, is_quiet: bool) -> Result<(), Error> {
         if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+            return Err(Error::NotScaffolded);
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        let mut cmd = Command::new("cargo");
+        cmd.args(["build", "--bin", &day.to_string()])
+            .args(options.to_cargo_args());
 
-        if is_release {
-            args.push("--release");
+        if is_quiet {
+            cmd.arg("--quiet");
         }
 
-        if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
+        if cmd.status()?.success() {
+            Ok(())
+        } else {
+            Err(Error::BuildFailed)
         }
+    }
+
+    /// Run the solution bin for a given day, passing its records and any other lines it prints to `on_output`.
+    /// The code that the binary exits with is the failure of the run, like for runs in the current process.
+    pub fn run_solution(
+        day: Day,
+        options: &RunOptions,
+        mut on_output: impl FnMut(Output),
+    ) -> Result<SolutionRun, Error> {
+        build_solution(day, options, true)?;
+
+        let day_padded = day.to_string();
+        let cargo_args = options.to_cargo_args();
+        let solution_args = options.to_solution_args();
+        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        args.extend(cargo_args.iter().map(String::as_str));
+
+        // children always report in json, so that their results do not need to be scraped from text.
+        args.extend(["--", "--format", "json"]);
+        // mirror `--time`, `--timeout` and input flags to child invocations.
+        args.extend(solution_args.iter().map(String::as_str));
 
         // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        // forward output to stdout/stderr while grabbing records from stdout.
 
         let mut cmd = Command::new("cargo")
             .args(&args)
@@ -105,150 +545,123 @@ mod child_commands {
         let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
         let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
 
-        let mut output = vec![];
+        let mut results = vec![];
+
+        // both streams are passed to `on_output` as their lines arrive, so that callers can buffer all of them.
+        let (sender, receiver) = mpsc::channel();
+        let stderr_sender = sender.clone();
 
-        let thread = thread::spawn(move || {
+        let stderr_thread = thread::spawn(move || {
             stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
+                let _ = stderr_sender.send(Output::ErrorLine(line.unwrap()));
             });
         });
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
-        }
-
-        thread.join().unwrap();
-        cmd.wait()?;
-
-        Ok(output)
-    }
-
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timings {
-        let mut timings = super::Timings {
-            day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
-        };
-
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
-
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
+        let stdout_thread = thread::spawn(move || {
+            stdout.lines().for_each(|line| {
+                let line = line.unwrap();
+                let output = match parse_record(&line) {
+                    Some(result) => Output::Record(Box::new(result)),
+                    // anything else was printed by the solution itself.
+                    None => Output::Line(line),
                 };
-
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
-                }
-
-                timings.total_nanos += nanos;
+                let _ = sender.send(output);
             });
+        });
 
-        timings
-    }
-
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
-
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
+        for output in receiver {
+            if let Output::Record(result) = &output {
+                results.push(result.as_ref().clone());
+            }
+            on_output(output);
+        }
 
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
+        stderr_thread.join().unwrap();
+        stdout_thread.join().unwrap();
+        let status = cmd.wait()?;
 
-        Some((str_timing, parsed_timing))
+        // solutions report failing parts through their records, any other exit code means that they crashed.
+        match status.code().and_then(ExitCode::from_code) {
+            Some(ExitCode::BuildFailed | ExitCode::NotScaffolded) | None => {
+                Err(Error::Crashed(format!("exited with {status}.")))
+            }
+            Some(failure) => Ok(SolutionRun { results, failure }),
+        }
     }
 
-    /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-    #[cfg(feature = "test_lib")]
-    macro_rules! assert_approx_eq {
-        ($a:expr, $b:expr) => {{
-            let (a, b) = (&$a, &$b);
-            assert!(
-                (*a - *b).abs() < 1.0e-6,
-                "{} is not approximately equal to {}",
-                *a,
-                *b
-            );
-        }};
+    pub fn parse_record(line: &str) -> Option<PartResult> {
+        serde_json::from_str(line).ok()
     }
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
-
+        use super::parse_record;
         use crate::day;
+        use crate::template::commands::all::collect_timings;
+        use crate::template::runner::PartStatus;
+        use std::time::Duration;
 
         #[test]
         fn test_well_formed() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+            let res = parse_record(
+                r#"{"day":1,"part":2,"answer":"10","duration_nanos":74130,"samples":99999,"status":"solved"}"#,
+            )
+            .unwrap();
+            assert_eq!(res.day, day!(1));
+            assert_eq!(res.part, 2);
+            assert_eq!(res.answer.as_deref(), Some("10"));
+            assert_eq!(res.duration, Duration::from_nanos(74130));
+            assert_eq!(res.samples, 99999);
+            assert_eq!(res.status, PartStatus::Solved);
         }
 
         #[test]
-        fn test_patterns_in_input() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+        fn test_output_of_solution() {
+            assert!(parse_record("Part 1: 0 (74.13ns @ 100000 samples)").is_none());
+            assert!(parse_record("{\"debug\": true}").is_none());
+            assert!(parse_record("").is_none());
+        }
+
+        #[test]
+        fn test_collect_timings() {
+            let records = [
+                r#"{"day":1,"part":1,"answer":"0","duration_nanos":74,"samples":100000,"status":"solved"}"#,
+                r#"{"day":1,"part":2,"answer":"10","duration_nanos":74130000,"samples":99999,"status":"solved"}"#,
+            ]
+            .map(|l| parse_record(l).unwrap());
+
+            let res = collect_timings(day!(1), &records, true);
+            assert_eq!(res.total_nanos, 74130074_f64);
+            assert_eq!(res.part_1.unwrap().mean, "74.0ns");
+            assert_eq!(res.part_2.unwrap().mean, "74.1ms");
+        }
+
+        #[test]
+        fn test_failed_parts() {
+            let records = [
+                r#"{"day":1,"part":1,"answer":null,"duration_nanos":0,"samples":1,"status":"failed","error":"failed: bad input"}"#,
+                r#"{"day":1,"part":2,"answer":null,"duration_nanos":74,"samples":1,"status":"unsolved"}"#,
+            ]
+            .map(|l| parse_record(l).unwrap());
+
+            let res = collect_timings(day!(1), &records, true);
+            assert_eq!(res.total_nanos, 0_f64);
+            assert!(res.part_1.unwrap().failed);
+            assert!(res.part_2.is_none());
         }
 
         #[test]
         fn test_missing_parts() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 0_f64);
-            assert_eq!(res.part_1.is_none(), true);
-            assert_eq!(res.part_2.is_none(), true);
+            let records = [
+                r#"{"day":1,"part":1,"answer":null,"duration_nanos":74,"samples":1,"status":"unsolved"}"#,
+                r#"{"day":1,"part":2,"answer":null,"duration_nanos":74,"samples":1,"status":"unsolved"}"#,
+            ]
+            .map(|l| parse_record(l).unwrap());
+
+            let res = collect_timings(day!(1), &records, true);
+            assert_eq!(res.total_nanos, 0_f64);
+            assert!(res.part_1.is_none());
+            assert!(res.part_2.is_none());
         }
     }
 }
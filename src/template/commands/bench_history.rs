@@ -0,0 +1,76 @@
+use std::process;
+
+use crate::template::{
+    bench_history::{self, Trend},
+    ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+};
+use crate::Day;
+
+/// Show how the benchmarks of every part developed, and flag parts that got slower than `threshold` percent
+/// compared with the `baseline` commit, or with the previous run if no baseline is given.
+pub fn handle(day: Option<Day>, baseline: Option<&str>, threshold: f64) {
+    let records = match bench_history::read() {
+        Ok(records) => records,
+        Err(e) => {
+            eprintln!("failed to read \"{}\": {e}", bench_history::HISTORY_PATH);
+            process::exit(1);
+        }
+    };
+
+    let trends: Vec<Trend> = bench_history::trends(&records, baseline)
+        .into_iter()
+        .filter(|trend| day.is_none_or(|day| trend.day == day))
+        .collect();
+
+    if trends.is_empty() {
+        println!("No benchmarks recorded yet. Run \"cargo time\" to record some.");
+        return;
+    }
+
+    let mut regressions = 0;
+
+    for (i, trend) in trends.iter().enumerate() {
+        if i == 0 || trends[i - 1].day != trend.day {
+            if i > 0 {
+                println!();
+            }
+
+            println!("{ANSI_BOLD}Day {}{ANSI_RESET}", trend.day);
+            println!("------");
+        }
+
+        let comparison = match (&trend.baseline, trend.change()) {
+            (Some(baseline), Some(change)) => {
+                let flag = if trend.is_regression(threshold) {
+                    regressions += 1;
+                    format!(" {ANSI_BOLD}⚠ regression{ANSI_RESET}")
+                } else {
+                    String::new()
+                };
+
+                format!(
+                    " {change:+.1}% vs {:.1?} at {}{flag}",
+                    baseline.stats.mean,
+                    baseline.label()
+                )
+            }
+            _ => format!(" {ANSI_ITALIC}no baseline{ANSI_RESET}"),
+        };
+
+        println!(
+            "{}: {} {:.1?} at {}{comparison}",
+            trend.label(),
+            bench_history::sparkline(&trend.means),
+            trend.latest.stats.mean,
+            trend.latest.label(),
+        );
+    }
+
+    let compared_with = baseline.map_or_else(|| "the previous run".into(), |x| format!("{x:?}"));
+
+    if regressions > 0 {
+        println!("\n{regressions} part(s) got slower by more than {threshold}% compared with {compared_with}.");
+    } else {
+        println!("\nNo regressions beyond {threshold}% compared with {compared_with}.");
+    }
+}
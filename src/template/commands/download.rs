@@ -1,10 +1,116 @@
 use crate::template::aoc_cli;
-use crate::Day;
-use std::process;
+use crate::template::aoc_client::{self, AocClient, ClientError};
+use crate::template::aoc_year;
+use crate::{Day, SystemClock};
+use std::{
+    fs,
+    io::{stdout, Write},
+    process, thread,
+    time::Duration,
+};
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+/// How often a download is retried when the puzzle is not found right after it unlocked, as clocks may differ.
+const UNLOCK_RETRIES: u32 = 5;
+
+/// Download the input and description of a day. With `wait`, a day that is not unlocked yet is downloaded as
+/// soon as it unlocks, otherwise it is refused.
+pub fn handle(day: Day, wait: bool) {
+    let remaining =
+        aoc_year().and_then(|year| Some((year, day.time_until_unlock(year, &SystemClock)?)));
+
+    if let Some((year, remaining)) = remaining {
+        if !wait {
+            eprintln!(
+                "day {day} of {year} unlocks in {}. Pass `--wait` to download it then.",
+                format_countdown(remaining)
+            );
+            process::exit(1);
+        }
+        wait_for_unlock(day, year);
+    }
+
+    if aoc_client::uses_aoc_cli() {
+        download_with_aoc_cli(day);
+        return;
+    }
+
+    let client = AocClient::from_env().unwrap_or_else(|e| {
+        eprintln!("failed to download day {day}: {e}");
+        process::exit(1);
+    });
+
+    let mut result = download(&client, day);
+    for _ in 0..if wait { UNLOCK_RETRIES } else { 0 } {
+        if !matches!(result, Err(ClientError::NotFound)) {
+            break;
+        }
+        thread::sleep(Duration::from_secs(2));
+        result = download(&client, day);
+    }
+
+    if let Err(e) = result {
+        eprintln!("failed to download day {day}: {e}");
+        process::exit(1);
+    }
+}
+
+/// Show a countdown until the puzzle of `day` unlocks.
+fn wait_for_unlock(day: Day, year: u16) {
+    while let Some(remaining) = day.time_until_unlock(year, &SystemClock) {
+        print!(
+            "\r⏳ Day {day} unlocks in {}. ",
+            format_countdown(remaining)
+        );
+        let _ = stdout().flush();
+
+        // wake up on full seconds, so that the countdown ticks evenly and ends right at the unlock.
+        let tick = match remaining.subsec_nanos() {
+            0 => Duration::from_secs(1),
+            nanos => Duration::from_nanos(u64::from(nanos)),
+        };
+        thread::sleep(tick);
+    }
+
+    println!("\r🔓 Day {day} is unlocked.{}", " ".repeat(24));
+}
+
+/// Format the time until an unlock like `1d 02h 03m 04s`, rounded up to full seconds.
+#[must_use]
+pub fn format_countdown(duration: Duration) -> String {
+    let seconds = duration.as_secs() + u64::from(duration.subsec_nanos() > 0);
+    let (days, hours, minutes, seconds) = (
+        seconds / 86_400,
+        seconds / 3600 % 24,
+        seconds / 60 % 60,
+        seconds % 60,
+    );
+
+    match (days, hours, minutes) {
+        (0, 0, 0) => format!("{seconds}s"),
+        (0, 0, _) => format!("{minutes}m {seconds:02}s"),
+        (0, _, _) => format!("{hours}h {minutes:02}m {seconds:02}s"),
+        _ => format!("{days}d {hours:02}h {minutes:02}m {seconds:02}s"),
+    }
+}
+
+fn download(client: &AocClient, day: Day) -> Result<(), aoc_client::ClientError> {
+    let input_path = format!("data/inputs/{day}.txt");
+    let puzzle_path = aoc_client::get_puzzle_path(day);
+
+    let input = client.input(day)?;
+    let puzzle = client.puzzle(day)?;
+
+    fs::write(&input_path, input)?;
+    aoc_client::save_puzzle(day, &puzzle)?;
+
+    println!("🎄 Successfully wrote input to \"{input_path}\".");
+    println!("🎄 Successfully wrote puzzle to \"{puzzle_path}\".");
+    Ok(())
+}
+
+fn download_with_aoc_cli(day: Day) {
+    if let Err(e) = aoc_cli::check() {
+        eprintln!("{e}");
         process::exit(1);
     }
 
@@ -13,3 +119,20 @@ pub fn handle(day: Day) {
         process::exit(1);
     };
 }
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::format_countdown;
+    use std::time::Duration;
+
+    #[test]
+    fn formats_countdown() {
+        assert_eq!(format_countdown(Duration::from_millis(4_200)), "5s");
+        assert_eq!(format_countdown(Duration::from_secs(65)), "1m 05s");
+        assert_eq!(format_countdown(Duration::from_secs(3600)), "1h 00m 00s");
+        assert_eq!(
+            format_countdown(Duration::from_secs(93_784)),
+            "1d 02h 03m 04s"
+        );
+    }
+}
@@ -0,0 +1,107 @@
+use std::{fs, process};
+
+use crate::template::{
+    aoc_client,
+    examples::{self, Example, Puzzle},
+};
+use crate::Day;
+
+/// Extract the examples of a day from its puzzle description.
+/// `block` is the number of a code block as listed by `--list`, starting at 1.
+pub fn handle(day: Day, part: Option<u8>, block: Option<usize>, list: bool) {
+    let puzzle_path = aoc_client::get_puzzle_path(day);
+    let Ok(markdown) = fs::read_to_string(&puzzle_path) else {
+        eprintln!(
+            "no puzzle description found at \"{puzzle_path}\". Run `cargo download {day}` first."
+        );
+        process::exit(1);
+    };
+
+    let puzzle = Puzzle::parse(&markdown);
+
+    if list {
+        print_blocks(&puzzle);
+        return;
+    }
+
+    if block == Some(0) {
+        eprintln!("code blocks are numbered starting at 1.");
+        process::exit(1);
+    }
+
+    let block = block.map(|block| block - 1);
+    let parts: Vec<u8> = match (part, block) {
+        (Some(part), _) => vec![part],
+        (None, Some(block)) => puzzle
+            .blocks
+            .get(block)
+            .map(|block| vec![block.part])
+            .unwrap_or_default(),
+        (None, None) => (1..=puzzle.parts).collect(),
+    };
+
+    let found: Vec<Example> = parts
+        .iter()
+        .filter_map(|part| puzzle.example(*part, block))
+        .collect();
+
+    if found.is_empty() {
+        eprintln!("no example found in \"{puzzle_path}\". Try `cargo examples {day} --list`.");
+        process::exit(1);
+    }
+
+    let text = |example: &Example| {
+        // a part without a block of its own reuses the example of part one.
+        example
+            .block
+            .or_else(|| puzzle.example(1, None)?.block)
+            .map(|block| puzzle.blocks[block].text.as_str())
+    };
+
+    // both parts share a file unless their examples differ, or a single part was asked for.
+    let is_shared = part.is_none()
+        && block.is_none()
+        && found
+            .windows(2)
+            .all(|pair| text(&pair[0]) == text(&pair[1]));
+
+    for example in &found {
+        if is_shared && example.part != 1 {
+            continue;
+        }
+
+        let path = examples::get_example_path(day, (!is_shared).then_some(example.part));
+        let Some(text) = text(example) else {
+            continue;
+        };
+
+        match fs::write(&path, text) {
+            Ok(()) => println!("🎄 Successfully wrote example to \"{path}\"."),
+            Err(e) => {
+                eprintln!("failed to write example: {e}");
+                process::exit(1);
+            }
+        }
+    }
+
+    for example in &found {
+        let Some(answer) = &example.answer else {
+            println!("No expected answer found for part {}.", example.part);
+            continue;
+        };
+
+        match examples::save_answer(day, example.part, answer) {
+            Ok(()) => println!("Expected answer of part {}: {answer}", example.part),
+            Err(e) => eprintln!("failed to save expected answer: {e}"),
+        }
+    }
+}
+
+fn print_blocks(puzzle: &Puzzle) {
+    for (i, block) in puzzle.blocks.iter().enumerate() {
+        let kind = if block.is_example { ", example" } else { "" };
+        println!("Block {} (part {}{kind}):", i + 1, block.part);
+        print!("{}", block.text);
+        println!();
+    }
+}
@@ -1,5 +1,97 @@
+use crate::template::{inputs::InputSelection, log::Level, runner::OutputFormat};
+
 pub mod all;
+pub mod bench_history;
 pub mod download;
+pub mod examples;
 pub mod read;
 pub mod scaffold;
 pub mod solve;
+
+/// Flags shared by `solve` and `all`.
+#[derive(Debug, Clone, Default)]
+pub struct RunOptions {
+    pub release: bool,
+    pub time: bool,
+    pub format: OutputFormat,
+    pub inputs: InputSelection,
+    /// Wall-clock limit per part in seconds, see `--timeout`.
+    pub timeout: Option<f64>,
+    /// Build solutions with the `memory` feature, see [`crate::template::memory`].
+    pub memory: bool,
+    /// Build solutions with the `spans` feature, see [`crate::template::spans`].
+    pub spans: bool,
+    /// Replay the frames that solutions draw, see [`crate::template::visualize`]. Only used by `solve`.
+    pub visualize: bool,
+    /// Export the frames that solutions draw to this file, see `--export`. Only used by `solve`.
+    pub export: Option<String>,
+    /// Frames per second of replays and exported animations, see `--fps`.
+    pub fps: Option<f64>,
+    /// Show messages of `dbg_log!` up to this level, see `--verbose`. Only used by `solve`.
+    pub log_level: Option<Level>,
+}
+
+impl RunOptions {
+    /// The arguments that are passed to `cargo run` when building a solution binary.
+    #[must_use]
+    pub fn to_cargo_args(&self) -> Vec<String> {
+        let mut args = vec![];
+
+        if self.release {
+            args.push("--release".into());
+        }
+
+        let features: Vec<&str> = [
+            (self.memory, "memory"),
+            (self.spans, "spans"),
+            (self.visualize || self.export.is_some(), "visualize"),
+        ]
+        .into_iter()
+        .filter_map(|(is_enabled, feature)| is_enabled.then_some(feature))
+        .collect();
+
+        if !features.is_empty() {
+            args.push("--features".into());
+            args.push(features.join(","));
+        }
+
+        args
+    }
+
+    /// The arguments that are forwarded to a solution binary, except for the output format.
+    #[must_use]
+    pub fn to_solution_args(&self) -> Vec<String> {
+        let mut args = vec![];
+
+        if self.time {
+            args.push("--time".into());
+        }
+
+        if let Some(timeout) = self.timeout {
+            args.push("--timeout".into());
+            args.push(timeout.to_string());
+        }
+
+        if self.visualize {
+            args.push("--visualize".into());
+        }
+
+        if let Some(export) = &self.export {
+            args.push("--export".into());
+            args.push(export.clone());
+        }
+
+        if let Some(fps) = self.fps {
+            args.push("--fps".into());
+            args.push(fps.to_string());
+        }
+
+        if let Some(level) = self.log_level {
+            args.push("--log-level".into());
+            args.push(level.to_string());
+        }
+
+        args.extend(self.inputs.to_args());
+        args
+    }
+}
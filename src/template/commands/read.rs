@@ -1,11 +1,96 @@
-use std::process;
+use std::{
+    env, fs,
+    io::{self, IsTerminal, Write},
+    process::{self, Command, Stdio},
+};
 
 use crate::template::aoc_cli;
+use crate::template::aoc_client::{self, markdown, AocClient};
 use crate::Day;
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+/// Show the description of a day, or of one of its parts. The description that `download` cached is read offline,
+/// it is only fetched if there is none yet.
+pub fn handle(day: Day, part: Option<u8>, no_pager: bool) {
+    let puzzle_path = aoc_client::get_puzzle_path(day);
+
+    let puzzle = match fs::read_to_string(&puzzle_path) {
+        Ok(puzzle) => puzzle,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            if aoc_client::uses_aoc_cli() {
+                read_with_aoc_cli(day);
+                return;
+            }
+            fetch(day)
+        }
+        Err(e) => {
+            eprintln!("failed to read \"{puzzle_path}\": {e}");
+            process::exit(1);
+        }
+    };
+
+    let parts = markdown::split_parts(&puzzle);
+    let puzzle = match part {
+        None => puzzle.as_str(),
+        Some(part) => match parts.get(usize::from(part).wrapping_sub(1)) {
+            Some(description) => description,
+            None => {
+                eprintln!("\"{puzzle_path}\" does not describe part {part}. If it is unlocked, run `cargo download {day}` to update it.");
+                process::exit(1);
+            }
+        },
+    };
+
+    show(&markdown::to_ansi(puzzle.trim()), no_pager);
+}
+
+fn fetch(day: Day) -> String {
+    let puzzle = AocClient::from_env()
+        .and_then(|client| client.puzzle(day))
+        .unwrap_or_else(|e| {
+            eprintln!("failed to read day {day}: {e}");
+            process::exit(1);
+        });
+
+    if let Err(e) = aoc_client::save_puzzle(day, &puzzle) {
+        eprintln!("failed to save puzzle: {e}");
+    }
+
+    puzzle
+}
+
+/// Print `text`, through the pager in `PAGER` or else `less` if stdout is a terminal.
+fn show(text: &str, no_pager: bool) {
+    if !no_pager && io::stdout().is_terminal() && page(text).is_ok() {
+        return;
+    }
+
+    print!("{text}");
+}
+
+fn page(text: &str) -> io::Result<()> {
+    let pager = env::var("PAGER").unwrap_or_else(|_| "less".into());
+    let mut args = pager.split_whitespace();
+    let program = args.next().ok_or(io::ErrorKind::NotFound)?;
+
+    let mut command = Command::new(program);
+    command.args(args).stdin(Stdio::piped());
+    if program == "less" {
+        // keep colors, and leave descriptions that fit on one screen on it.
+        command.env("LESS", "-R -F -X");
+    }
+
+    let mut child = command.spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        // the pager may be closed before all of the text was written.
+        let _ = stdin.write_all(text.as_bytes());
+    }
+    child.wait()?;
+    Ok(())
+}
+
+fn read_with_aoc_cli(day: Day) {
+    if let Err(e) = aoc_cli::check() {
+        eprintln!("{e}");
         process::exit(1);
     }
 
@@ -4,7 +4,8 @@ use std::{
     process,
 };
 
-use crate::Day;
+use crate::template::{aoc_year, commands::download::format_countdown};
+use crate::{Day, SystemClock};
 
 const MODULE_TEMPLATE: &str = r#"advent_of_code::solution!(DAY_NUMBER);
 
@@ -19,17 +20,18 @@ pub fn part_two(input: &str) -> Option<u32> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::template::examples;
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        let result = part_one(&examples::read_example(DAY, 1));
+        assert_eq!(result, examples::expected_answer(DAY, 1));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        let result = part_two(&examples::read_example(DAY, 2));
+        assert_eq!(result, examples::expected_answer(DAY, 2));
     }
 }
 "#;
@@ -39,7 +41,11 @@ fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
 }
 
 fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create(true).open(path)
+    OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
 }
 
 pub fn handle(day: Day) {
@@ -91,4 +97,12 @@ pub fn handle(day: Day) {
 
     println!("---");
     println!("🎄 Type `cargo solve {}` to run your solution.", day);
+
+    let remaining = aoc_year().and_then(|year| day.time_until_unlock(year, &SystemClock));
+    if let Some(remaining) = remaining {
+        println!(
+            "🔒 The puzzle unlocks in {}. Type `cargo download {day} --wait` to download it then.",
+            format_countdown(remaining)
+        );
+    }
 }
@@ -1,14 +1,56 @@
-use std::process::{Command, Stdio};
+use std::process::{self, Command, Stdio};
+use std::time::Duration;
 
+use crate::template::{
+    commands::{all, RunOptions},
+    exit_code::ExitCode,
+    inputs::{InputSelection, InputSource},
+    runner::{OutputFormat, PartResult},
+    watch::{self, Snapshot},
+    ANSI_BOLD, ANSI_CLEAR, ANSI_ITALIC, ANSI_RESET,
+};
 use crate::Day;
 
-pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+/// How often files are checked for changes in `--watch` mode.
+const POLL_INTERVAL: Duration = Duration::from_millis(300);
 
-    if release {
-        cmd_args.push("--release".to_string());
+pub fn handle(day: Day, submit_part: Option<u8>, is_watch: bool, options: &RunOptions) {
+    if is_watch {
+        if submit_part.is_some() || options.inputs == InputSelection::Single(InputSource::Stdin) {
+            eprintln!("--watch cannot be combined with --submit or --input -.");
+            process::exit(1);
+        }
+
+        watch(day, options);
+    }
+
+    match all::build_solution(day, options, false) {
+        Ok(()) => {}
+        Err(all::Error::NotScaffolded) => {
+            eprintln!(
+                "Day {day} has not been scaffolded yet. Run \"cargo scaffold {day}\" to create it."
+            );
+            ExitCode::NotScaffolded.exit();
+        }
+        Err(all::Error::BuildFailed) => {
+            eprintln!("Day {day}: build failed.");
+            ExitCode::BuildFailed.exit();
+        }
+        Err(e) => {
+            eprintln!("{e}");
+            ExitCode::Error.exit();
+        }
     }
 
+    let mut cmd_args = vec![
+        "run".to_string(),
+        "--quiet".to_string(),
+        "--bin".to_string(),
+        day.to_string(),
+    ];
+
+    cmd_args.extend(options.to_cargo_args());
+
     cmd_args.push("--".to_string());
 
     if let Some(submit_part) = submit_part {
@@ -16,16 +58,96 @@ pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
         cmd_args.push(submit_part.to_string());
     }
 
-    if time {
-        cmd_args.push("--time".to_string());
+    if options.format == OutputFormat::Json {
+        cmd_args.push("--format".to_string());
+        cmd_args.push("json".to_string());
     }
 
-    let mut cmd = Command::new("cargo")
+    cmd_args.extend(options.to_solution_args());
+
+    let status = Command::new("cargo")
         .args(&cmd_args)
+        .stdin(Stdio::inherit())
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
-        .spawn()
-        .unwrap();
+        .status();
+
+    let exit_code = match status {
+        Ok(status) => status
+            .code()
+            .and_then(ExitCode::from_code)
+            .unwrap_or_else(|| {
+                eprintln!("The solution exited with {status}.");
+                ExitCode::RuntimeFailure
+            }),
+        Err(e) => {
+            eprintln!("Could not run the solution: {e}");
+            ExitCode::Error
+        }
+    };
+
+    if exit_code != ExitCode::Success {
+        eprintln!("\nDay {day}: {exit_code}.");
+        exit_code.exit();
+    }
+}
+
+/// Rebuild and re-run a day whenever one of its files changes, see [`watch::watched_paths`].
+fn watch(day: Day, options: &RunOptions) -> ! {
+    let paths = watch::watched_paths(day);
+    let is_text = options.format == OutputFormat::Text;
+    let mut previous: Option<Vec<PartResult>> = None;
+
+    loop {
+        let snapshot = Snapshot::take(&paths);
+
+        if is_text {
+            print!("{ANSI_CLEAR}");
+            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+            println!("------");
+        }
 
-    cmd.wait().unwrap();
+        let results = all::run_binary(day, options);
+
+        if is_text {
+            if let Some(previous) = &previous {
+                print_answer_changes(previous, &results);
+            }
+
+            println!("\n{ANSI_ITALIC}Watching for changes...{ANSI_RESET}");
+        }
+
+        // keep comparing against the last successful run if the build failed.
+        if !results.is_empty() {
+            previous = Some(results);
+        }
+
+        snapshot.wait_for_change(&paths, POLL_INTERVAL);
+    }
+}
+
+fn print_answer_changes(previous: &[PartResult], current: &[PartResult]) {
+    let changes = watch::answer_changes(previous, current);
+
+    if changes.is_empty() {
+        if !current.is_empty() {
+            println!("\n{ANSI_ITALIC}No answers changed since the previous run.{ANSI_RESET}");
+        }
+        return;
+    }
+
+    println!("\n{ANSI_BOLD}Changes since the previous run:{ANSI_RESET}");
+
+    for change in changes {
+        let input = change
+            .input
+            .map_or_else(String::new, |name| format!(" ({name})"));
+
+        println!(
+            "Part {}{input}: {} → {}",
+            change.part,
+            change.previous.as_deref().unwrap_or("✖"),
+            change.current.as_deref().unwrap_or("✖"),
+        );
+    }
 }
@@ -0,0 +1,336 @@
+/// Module that extracts example inputs and their expected answers from the puzzle descriptions in `data/puzzles`.
+/// Examples are code blocks that are introduced by a sentence mentioning an example, like "For example:". Their
+/// expected answer is the last emphasized value that follows them, like *`142`*, before the next example.
+///
+/// Expected answers are stored in `data/examples/DD.toml`, in the same format as [`crate::template::answers`].
+use std::{env, fs, path::PathBuf, str::FromStr};
+
+use crate::template::{
+    answers::{self, Answers},
+    aoc_client::markdown,
+};
+use crate::Day;
+
+/// A fenced code block of a puzzle description.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodeBlock {
+    /// The part whose description contains the block.
+    pub part: u8,
+    pub text: String,
+    /// Whether the paragraph before the block mentions an example.
+    pub is_example: bool,
+}
+
+/// The example input of a part along with its expected answer, if the description emphasizes one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    pub part: u8,
+    /// The index of the block in [`Puzzle::blocks`], or `None` if the part reuses the example of part one.
+    pub block: Option<usize>,
+    pub answer: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Item {
+    Block(usize),
+    Answer(String),
+}
+
+/// The code blocks and emphasized values of a puzzle description, in order.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Puzzle {
+    pub blocks: Vec<CodeBlock>,
+    /// The number of parts that are described, i.e. 2 once part one is solved.
+    pub parts: u8,
+    items: Vec<(u8, Item)>,
+}
+
+impl Puzzle {
+    /// Parse a puzzle description in markdown, as written by the `download` and `read` commands.
+    #[must_use]
+    pub fn parse(markdown: &str) -> Self {
+        let mut puzzle = Puzzle::default();
+
+        for (part, markdown) in (1..).zip(markdown::split_parts(markdown)) {
+            puzzle.parts = part;
+            puzzle.parse_part(part, markdown);
+        }
+
+        puzzle
+    }
+
+    fn parse_part(&mut self, part: u8, markdown: &str) {
+        // the paragraph before the current line, which introduces code blocks.
+        let mut paragraph = String::new();
+        let mut is_paragraph_done = false;
+        let mut lines = markdown.lines();
+
+        while let Some(line) = lines.next() {
+            if line.trim_start().starts_with("```") {
+                let text: Vec<&str> = lines
+                    .by_ref()
+                    .take_while(|line| !line.trim_start().starts_with("```"))
+                    .collect();
+                self.items.push((part, Item::Block(self.blocks.len())));
+                self.blocks.push(CodeBlock {
+                    part,
+                    text: text.join("\n") + "\n",
+                    is_example: paragraph.to_lowercase().contains("example"),
+                });
+                paragraph.clear();
+            } else if line.trim().is_empty() {
+                is_paragraph_done = true;
+            } else {
+                if is_paragraph_done {
+                    paragraph.clear();
+                    is_paragraph_done = false;
+                }
+                paragraph.push_str(line);
+                paragraph.push('\n');
+                for answer in emphasized_values(line) {
+                    self.items.push((part, Item::Answer(answer)));
+                }
+            }
+        }
+    }
+
+    /// The example of `part`, either the block at `block` or the first block that is introduced as an example.
+    /// Part one falls back to its first block, part two to the example of part one.
+    /// Returns `None` if the part is not described yet, or if it has no code blocks.
+    #[must_use]
+    pub fn example(&self, part: u8, block: Option<usize>) -> Option<Example> {
+        if part == 0 || part > self.parts {
+            return None;
+        }
+
+        let in_part = |i: &usize| self.blocks[*i].part == part;
+        let block = match block {
+            Some(block) if block >= self.blocks.len() => return None,
+            Some(block) => Some(block),
+            None => (0..self.blocks.len())
+                .filter(in_part)
+                .find(|i| self.blocks[*i].is_example)
+                .or_else(|| (0..self.blocks.len()).find(in_part).filter(|_| part == 1)),
+        };
+
+        if block.is_none() && part == 1 {
+            return None;
+        }
+
+        // the answer follows the block, or the start of the part if it reuses an example of another part.
+        let start = self
+            .items
+            .iter()
+            .position(|(item_part, item)| match block.filter(in_part) {
+                Some(block) => *item == Item::Block(block),
+                None => *item_part == part,
+            })
+            .unwrap_or(self.items.len());
+
+        let answer = self.items[start..]
+            .iter()
+            .enumerate()
+            .take_while(|(i, (item_part, item))| {
+                let is_next_example =
+                    *i > 0 && matches!(item, Item::Block(j) if self.blocks[*j].is_example);
+                *item_part == part && !is_next_example
+            })
+            .filter_map(|(_, (_, item))| match item {
+                Item::Answer(answer) => Some(answer.clone()),
+                Item::Block(_) => None,
+            })
+            .last();
+
+        Some(Example {
+            part,
+            block,
+            answer,
+        })
+    }
+}
+
+/// Values that are emphasized code in `line`, like *`142`*.
+fn emphasized_values(line: &str) -> Vec<String> {
+    let mut values = vec![];
+    let mut rest = line;
+
+    while let Some(start) = rest.find("*`") {
+        rest = &rest[start + 2..];
+        let Some(end) = rest.find("`*") else {
+            break;
+        };
+        values.push(rest[..end].to_string());
+        rest = &rest[end + 2..];
+    }
+
+    values
+}
+
+/// The path of the example of `part`, like `data/examples/01-2.txt`, or of both parts if `part` is `None`.
+#[must_use]
+pub fn get_example_path(day: Day, part: Option<u8>) -> String {
+    match part {
+        Some(part) => format!("data/examples/{day}-{part}.txt"),
+        None => format!("data/examples/{day}.txt"),
+    }
+}
+
+#[must_use]
+pub fn get_answers_path(day: Day) -> String {
+    format!("data/examples/{day}.toml")
+}
+
+/// Read the expected answers of the examples of a day.
+pub fn read_answers(day: Day) -> Result<Answers, answers::Error> {
+    answers::read_from(&get_answers_path(day))
+}
+
+/// Store the expected answer of the example of one part, keeping the answer of the other part intact.
+pub fn save_answer(day: Day, part: u8, answer: &str) -> Result<(), answers::Error> {
+    answers::save_to(&get_answers_path(day), part, answer)
+}
+
+/// Helper function that reads the example of a part, `data/examples/DD-P.txt` if it exists and else
+/// `data/examples/DD.txt`.
+#[must_use]
+pub fn read_example(day: Day, part: u8) -> String {
+    let cwd = env::current_dir().unwrap();
+    let path: PathBuf = [
+        get_example_path(day, Some(part)),
+        get_example_path(day, None),
+    ]
+    .into_iter()
+    .map(|path| cwd.join(path))
+    .find(|path| path.exists())
+    .unwrap_or_else(|| cwd.join(get_example_path(day, None)));
+
+    fs::read_to_string(path).expect("could not open example file")
+}
+
+/// Helper function that returns the expected answer of the example of a part, as stored by the `examples` command.
+/// Returns `None` if no answer is known, which matches an unsolved part in tests.
+///
+/// # Panics
+///
+/// Panics if the stored answer cannot be parsed as `T`.
+#[must_use]
+pub fn expected_answer<T: FromStr>(day: Day, part: u8) -> Option<T> {
+    let answers = read_answers(day).unwrap_or_else(|e| panic!("{e}"));
+    answers.get(part).map(|answer| {
+        answer.parse().unwrap_or_else(|_| {
+            panic!("expected answer {answer:?} of part {part} has an unexpected type")
+        })
+    })
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{emphasized_values, Example, Puzzle};
+
+    const PUZZLE: &str = "## --- Day 4: Scratchcards ---
+
+The Elf leads you over to the pile.
+
+For example:
+
+```
+Card 1: 41 48 | 83 86
+Card 2: 13 32 | 61 30
+```
+
+In the above example, card 1 is worth *`8`* points and card 2 is worth *`2`* points.
+
+So, in this example, the pile is worth *`10`* points.
+
+## --- Part Two ---
+
+A card that looks like this:
+
+```
+Card 3: 1 | 2
+```
+
+Process all of the cards. In this example, you end up with *`30`* scratchcards.
+
+Here is a larger example:
+
+```
+Card 1: 1 | 1
+```
+
+This one ends up with *`4`* scratchcards.";
+
+    #[test]
+    fn finds_code_blocks() {
+        let puzzle = Puzzle::parse(PUZZLE);
+
+        assert_eq!(puzzle.parts, 2);
+        assert_eq!(puzzle.blocks.len(), 3);
+        assert_eq!(
+            puzzle.blocks[0].text,
+            "Card 1: 41 48 | 83 86\nCard 2: 13 32 | 61 30\n"
+        );
+        assert!(puzzle.blocks[0].is_example);
+        assert!(!puzzle.blocks[1].is_example);
+        assert_eq!(puzzle.blocks[1].part, 2);
+    }
+
+    #[test]
+    fn selects_examples() {
+        let puzzle = Puzzle::parse(PUZZLE);
+
+        assert_eq!(
+            puzzle.example(1, None),
+            Some(Example {
+                part: 1,
+                block: Some(0),
+                answer: Some("10".into())
+            })
+        );
+        assert_eq!(
+            puzzle.example(2, None),
+            Some(Example {
+                part: 2,
+                block: Some(2),
+                answer: Some("4".into())
+            })
+        );
+        // a block that is not introduced as an example is only used when picked.
+        assert_eq!(
+            puzzle.example(2, Some(1)),
+            Some(Example {
+                part: 2,
+                block: Some(1),
+                answer: Some("30".into())
+            })
+        );
+        assert_eq!(puzzle.example(3, None), None);
+        assert_eq!(puzzle.example(1, Some(7)), None);
+    }
+
+    #[test]
+    fn reuses_example_of_part_one() {
+        let puzzle = Puzzle::parse(
+            "For example:\n\n```\n1\n```\n\nThat is *`1`*.\n\n## --- Part Two ---\n\nNow it is *`2`*.",
+        );
+
+        assert_eq!(
+            puzzle.example(2, None),
+            Some(Example {
+                part: 2,
+                block: None,
+                answer: Some("2".into())
+            })
+        );
+        assert_eq!(Puzzle::parse("Only text.").example(1, None), None);
+        assert_eq!(Puzzle::parse("Only text.").example(2, None), None);
+    }
+
+    #[test]
+    fn finds_emphasized_values() {
+        assert_eq!(
+            emphasized_values("a *`1`* b `2` *c* *`3,4`*"),
+            vec!["1".to_string(), "3,4".to_string()]
+        );
+    }
+}
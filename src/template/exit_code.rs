@@ -0,0 +1,119 @@
+/// Module with the exit codes of `solve`, `all` and solution binaries.
+/// Every kind of failure has its own code, so that CI and scripts can tell them apart. When several things went
+/// wrong, the most severe one is reported, which is the one declared last.
+use std::{fmt::Display, process};
+
+use crate::template::runner::{PartResult, PartStatus};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub enum ExitCode {
+    #[default]
+    Success = 0,
+    /// Invalid arguments, missing inputs and other errors that are not specific to a solution.
+    Error = 1,
+    /// An answer did not match the answer stored in `data/answers`.
+    WrongAnswer = 2,
+    /// An answer could not be submitted, or was not accepted.
+    SubmissionFailed = 3,
+    /// A part panicked, timed out or returned an error, or the solution binary crashed.
+    RuntimeFailure = 4,
+    /// The solution binary did not compile.
+    BuildFailed = 5,
+    /// The day has not been scaffolded yet.
+    NotScaffolded = 6,
+}
+
+impl ExitCode {
+    /// The code reported by a solution binary, or `None` if the binary exited in another way, e.g. by crashing.
+    #[must_use]
+    pub fn from_code(code: i32) -> Option<Self> {
+        [
+            Self::Success,
+            Self::Error,
+            Self::WrongAnswer,
+            Self::SubmissionFailed,
+            Self::RuntimeFailure,
+            Self::BuildFailed,
+            Self::NotScaffolded,
+        ]
+        .into_iter()
+        .find(|x| *x as i32 == code)
+    }
+
+    /// The most severe code for the results of a run.
+    #[must_use]
+    pub fn of_results(results: &[PartResult]) -> Self {
+        results
+            .iter()
+            .map(|result| {
+                if result.status.is_failure() {
+                    Self::RuntimeFailure
+                } else if result.status == PartStatus::Wrong {
+                    Self::WrongAnswer
+                } else {
+                    Self::Success
+                }
+            })
+            .max()
+            .unwrap_or_default()
+    }
+
+    /// Exit the process with this code.
+    pub fn exit(self) -> ! {
+        process::exit(self as i32)
+    }
+}
+
+impl Display for ExitCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let description = match self {
+            Self::Success => "success",
+            Self::Error => "error",
+            Self::WrongAnswer => "wrong answer",
+            Self::SubmissionFailed => "submission failed",
+            Self::RuntimeFailure => "runtime failure",
+            Self::BuildFailed => "build failed",
+            Self::NotScaffolded => "not scaffolded",
+        };
+
+        write!(f, "{description}")
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::ExitCode;
+    use crate::day;
+    use crate::template::runner::{PartResult, PartStatus};
+
+    fn result(status: PartStatus) -> PartResult {
+        PartResult {
+            status,
+            ..PartResult::new(day!(1), 1)
+        }
+    }
+
+    #[test]
+    fn reports_most_severe_result() {
+        assert_eq!(ExitCode::of_results(&[]), ExitCode::Success);
+        assert_eq!(
+            ExitCode::of_results(&[result(PartStatus::Solved), result(PartStatus::Unsolved)]),
+            ExitCode::Success
+        );
+        assert_eq!(
+            ExitCode::of_results(&[result(PartStatus::Wrong), result(PartStatus::Solved)]),
+            ExitCode::WrongAnswer
+        );
+        assert_eq!(
+            ExitCode::of_results(&[result(PartStatus::Wrong), result(PartStatus::Panicked)]),
+            ExitCode::RuntimeFailure
+        );
+    }
+
+    #[test]
+    fn round_trips_codes() {
+        assert_eq!(ExitCode::from_code(4), Some(ExitCode::RuntimeFailure));
+        assert_eq!(ExitCode::from_code(0), Some(ExitCode::Success));
+        assert_eq!(ExitCode::from_code(101), None);
+    }
+}
@@ -0,0 +1,219 @@
+/// Module with the harness behind `cargo bench`.
+/// The `solution!` macro registers a benchmark for every day, so `benches/days.rs` only has to pass the registry to
+/// [`main`]. The parse phase and both parts are benchmarked on the real input of a day, and the results are compared
+/// with the previous run through a [`baseline`] named `base`.
+use std::{
+    fmt::Display,
+    panic::{self, AssertUnwindSafe},
+    process,
+    time::{Duration, Instant},
+};
+
+use crate::template::{
+    baseline,
+    bench_history::{self, Record},
+    registry::Solution,
+    runner::{self, PARSE_PART},
+    stats::BenchStats,
+    try_read_file, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+};
+use crate::Day;
+
+/// The baseline that every run is compared with and saved as, unless `--save-baseline` or `--baseline` are passed.
+const DEFAULT_BASELINE: &str = "base";
+
+/// Receives the phases of a solution to benchmark, see [`Solution::bench`].
+pub trait Bencher {
+    /// Benchmark `func`, which runs a part of a solution, or its parse phase for [`PARSE_PART`].
+    fn bench(&mut self, part: u8, func: &dyn Fn());
+
+    /// Report that a part could not be benchmarked.
+    fn failed(&mut self, part: u8, error: &dyn Display);
+}
+
+/// Unwrap the output of a parse function, reporting errors to `bencher`. Used by the `solution!` macro.
+pub fn parsed<T, E: Into<anyhow::Error>>(
+    bencher: &mut dyn Bencher,
+    result: Result<T, E>,
+) -> Option<T> {
+    result
+        .map_err(|e| bencher.failed(PARSE_PART, &e.into()))
+        .ok()
+}
+
+struct Options {
+    /// Whether cargo runs the harness through `cargo bench`, as opposed to `cargo test --benches`.
+    /// When testing, every phase runs once to check that it works.
+    is_bench: bool,
+    days: Vec<Day>,
+    save: Option<String>,
+    compare: Option<String>,
+}
+
+impl Options {
+    fn from_args() -> Result<Self, pico_args::Error> {
+        let mut args = pico_args::Arguments::from_env();
+        let is_bench = args.contains("--bench");
+        let save: Option<String> = args.opt_value_from_str("--save-baseline")?;
+        let compare: Option<String> = args.opt_value_from_str("--baseline")?;
+
+        let mut days = vec![];
+
+        for filter in args.finish() {
+            match filter.to_string_lossy().parse::<Day>() {
+                Ok(day) => days.push(day),
+                Err(_) => eprintln!(
+                    "Warning: ignoring {filter:?}, filters are days like \"5\" or \"05\"."
+                ),
+            }
+        }
+
+        // by default, every run is compared with the previous one.
+        let (save, compare) = match (save, compare) {
+            (None, None) => (Some(DEFAULT_BASELINE.into()), Some(DEFAULT_BASELINE.into())),
+            options => options,
+        };
+
+        Ok(Self {
+            is_bench,
+            days,
+            save,
+            compare,
+        })
+    }
+}
+
+/// Benchmark every registered solution, or the days passed as arguments, e.g. `cargo bench -- 5`.
+pub fn main(solutions: &[Solution]) {
+    let options = Options::from_args().unwrap_or_else(|e| {
+        eprintln!("Error: {e}");
+        process::exit(1);
+    });
+
+    let baseline = match &options.compare {
+        Some(name) if options.is_bench => match baseline::load(name) {
+            Ok(records) => records,
+            // the default baseline does not exist before the first run.
+            Err(_) if name == DEFAULT_BASELINE => vec![],
+            Err(e) => {
+                eprintln!("could not read baseline \"{name}\": {e}");
+                process::exit(1);
+            }
+        },
+        _ => vec![],
+    };
+
+    let mut records = vec![];
+
+    for solution in solutions
+        .iter()
+        .filter(|solution| options.days.is_empty() || options.days.contains(&solution.day))
+    {
+        println!("\n{ANSI_BOLD}Day {}{ANSI_RESET}", solution.day);
+        println!("------");
+
+        let Ok(input) = try_read_file("inputs", solution.day) else {
+            println!("{ANSI_ITALIC}Skipped, as the input is missing.{ANSI_RESET}");
+            continue;
+        };
+
+        let mut harness = Harness {
+            day: solution.day,
+            is_bench: options.is_bench,
+            baseline: &baseline,
+            records: vec![],
+        };
+
+        if let Err(payload) =
+            panic::catch_unwind(AssertUnwindSafe(|| (solution.bench)(&input, &mut harness)))
+        {
+            println!("✖ panicked: {}", runner::panic_message(&*payload));
+        }
+
+        records.extend(harness.records);
+    }
+
+    if let (true, Some(name)) = (options.is_bench, &options.save) {
+        // keep the parts of a previous baseline that were filtered out of this run.
+        let previous: Vec<Record> = baseline::load(name)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|old| {
+                !records
+                    .iter()
+                    .any(|new| new.day == old.day && new.part == old.part)
+            })
+            .collect();
+        records.extend(previous);
+
+        match baseline::save(name, &records) {
+            Ok(()) => println!("\nSaved benchmarks as baseline \"{name}\"."),
+            Err(e) => eprintln!("\nFailed to save baseline \"{name}\": {e}"),
+        }
+    }
+}
+
+struct Harness<'a> {
+    day: Day,
+    is_bench: bool,
+    baseline: &'a [Record],
+    records: Vec<Record>,
+}
+
+impl Bencher for Harness<'_> {
+    fn bench(&mut self, part: u8, func: &dyn Fn()) {
+        let label = label(part);
+
+        let timer = Instant::now();
+        if let Err(payload) = panic::catch_unwind(AssertUnwindSafe(func)) {
+            println!("{label}: ✖ panicked: {}", runner::panic_message(&*payload));
+            return;
+        }
+        let base_time = timer.elapsed();
+
+        if !self.is_bench {
+            println!("{label}: ok ({base_time:.1?})");
+            return;
+        }
+
+        let samples = runner::sample(&|()| func(), (), &base_time);
+        let Some(stats) = BenchStats::from_samples(&samples) else {
+            return;
+        };
+
+        let record = Record {
+            commit: None,
+            dirty: false,
+            timestamp: bench_history::timestamp(),
+            day: self.day,
+            part,
+            samples: samples.len() as u128,
+            stats,
+        };
+
+        let comparison = &baseline::compare(self.baseline, std::slice::from_ref(&record))[0];
+        let spread = format_spread(&stats.std_dev, samples.len());
+
+        match comparison.old {
+            Some(_) => println!("{label}: {} · {spread}", comparison.summary()),
+            None => println!("{label}: {:.1?} · {spread}", stats.mean),
+        }
+
+        self.records.push(record);
+    }
+
+    fn failed(&mut self, part: u8, error: &dyn Display) {
+        println!("{}: ✖ {error}", label(part));
+    }
+}
+
+fn label(part: u8) -> String {
+    match part {
+        PARSE_PART => "Parse".into(),
+        part => format!("Part {part}"),
+    }
+}
+
+fn format_spread(std_dev: &Duration, samples: usize) -> String {
+    format!("{ANSI_ITALIC}σ {std_dev:.1?} @ {samples} samples{ANSI_RESET}")
+}
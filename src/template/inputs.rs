@@ -0,0 +1,248 @@
+/// Module that resolves which inputs a solution runs on.
+/// Besides the default `data/inputs/DD.txt`, solutions can read an arbitrary file, stdin,
+/// or named alternate inputs stored as `data/inputs/DD/<name>.txt`.
+use std::{
+    env,
+    error::Error,
+    fmt::Display,
+    fs,
+    io::{self, Read},
+    path::PathBuf,
+};
+
+use crate::Day;
+
+/// A single input that a solution can be run on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    /// `data/inputs/DD.txt`.
+    Default,
+    /// `data/inputs/DD/<name>.txt`.
+    Named(String),
+    File(PathBuf),
+    Stdin,
+}
+
+impl InputSource {
+    /// The named alternate input `name`. Names cannot contain path separators or `..`, so that they cannot point
+    /// outside of `data/inputs/DD`.
+    pub fn named(name: &str) -> Result<Self, InvalidInputName> {
+        let is_valid = !name.is_empty() && !name.contains(['/', '\\']) && !name.contains("..");
+
+        if is_valid {
+            Ok(InputSource::Named(name.into()))
+        } else {
+            Err(InvalidInputName(name.into()))
+        }
+    }
+
+    /// The label results on this input are reported with, `None` for the default input.
+    #[must_use]
+    pub fn name(&self) -> Option<String> {
+        match self {
+            InputSource::Default => None,
+            InputSource::Named(name) => Some(name.clone()),
+            InputSource::File(path) => Some(path.display().to_string()),
+            InputSource::Stdin => Some("stdin".into()),
+        }
+    }
+
+    pub fn read(&self, day: Day) -> io::Result<String> {
+        match self {
+            InputSource::Default => super::try_read_file("inputs", day),
+            InputSource::Named(name) => {
+                Self::named(name).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+                fs::read_to_string(get_named_input_path(day, name))
+            }
+            InputSource::File(path) => fs::read_to_string(path),
+            InputSource::Stdin => {
+                let mut input = String::new();
+                io::stdin().read_to_string(&mut input)?;
+                Ok(input)
+            }
+        }
+    }
+}
+
+/// An error which can be returned when an input name could point outside of `data/inputs/DD`, see
+/// [`InputSource::named`].
+#[derive(Debug)]
+pub struct InvalidInputName(pub String);
+
+impl Error for InvalidInputName {}
+
+impl Display for InvalidInputName {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "invalid input name {:?}, names cannot contain path separators or \"..\"",
+            self.0
+        )
+    }
+}
+
+/// The inputs selected on the command line.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum InputSelection {
+    /// Only the default input.
+    #[default]
+    Default,
+    /// A single input, selected with `--input <path>`, `--input -` or `--input-name <name>`.
+    Single(InputSource),
+    /// The default input followed by every named alternate input, selected with `--all-inputs`.
+    All,
+}
+
+impl InputSelection {
+    /// Read the selection from the arguments passed to the current process.
+    #[must_use]
+    pub fn from_args() -> Self {
+        let args: Vec<String> = env::args().collect();
+        Self::parse(&args)
+    }
+
+    fn parse(args: &[String]) -> Self {
+        let value_of = |flag: &str| {
+            args.iter()
+                .position(|x| x == flag)
+                .and_then(|i| args.get(i + 1))
+        };
+
+        if args.iter().any(|x| x == "--all-inputs") {
+            Self::All
+        } else if let Some(name) = value_of("--input-name") {
+            Self::Single(InputSource::Named(name.clone()))
+        } else if let Some(path) = value_of("--input") {
+            Self::from_path(path)
+        } else {
+            Self::Default
+        }
+    }
+
+    /// Select the file at `path`, or stdin if `path` is `-`.
+    #[must_use]
+    pub fn from_path(path: &str) -> Self {
+        if path == "-" {
+            Self::Single(InputSource::Stdin)
+        } else {
+            Self::Single(InputSource::File(path.into()))
+        }
+    }
+
+    /// The arguments that select the same inputs in a child invocation.
+    #[must_use]
+    pub fn to_args(&self) -> Vec<String> {
+        match self {
+            Self::Default => vec![],
+            Self::Single(InputSource::Default) => vec![],
+            Self::Single(InputSource::Named(name)) => vec!["--input-name".into(), name.clone()],
+            Self::Single(InputSource::File(path)) => {
+                vec!["--input".into(), path.display().to_string()]
+            }
+            Self::Single(InputSource::Stdin) => vec!["--input".into(), "-".into()],
+            Self::All => vec!["--all-inputs".into()],
+        }
+    }
+
+    /// List the inputs to run for a day.
+    #[must_use]
+    pub fn resolve(&self, day: Day) -> Vec<InputSource> {
+        match self {
+            Self::Default => vec![InputSource::Default],
+            Self::Single(source) => vec![source.clone()],
+            Self::All => {
+                let mut sources = vec![InputSource::Default];
+                sources.extend(named_inputs(day).into_iter().map(InputSource::Named));
+                sources
+            }
+        }
+    }
+}
+
+#[must_use]
+pub fn get_named_input_path(day: Day, name: &str) -> PathBuf {
+    PathBuf::from(format!("data/inputs/{day}/{name}.txt"))
+}
+
+/// Names of all alternate inputs of a day, sorted alphabetically.
+#[must_use]
+pub fn named_inputs(day: Day) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(format!("data/inputs/{day}")) else {
+        return vec![];
+    };
+
+    let mut names: Vec<String> = entries
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            if path.extension()? != "txt" {
+                return None;
+            }
+            Some(path.file_stem()?.to_str()?.to_string())
+        })
+        .collect();
+
+    names.sort();
+    names
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{InputSelection, InputSource};
+    use crate::day;
+    use std::io;
+
+    fn parse(args: &[&str]) -> InputSelection {
+        let args: Vec<String> = args.iter().map(|x| x.to_string()).collect();
+        InputSelection::parse(&args)
+    }
+
+    #[test]
+    fn parses_selection() {
+        assert_eq!(parse(&["05", "--time"]), InputSelection::Default);
+        assert_eq!(
+            parse(&["05", "--input", "-"]),
+            InputSelection::Single(InputSource::Stdin)
+        );
+        assert_eq!(
+            parse(&["05", "--input", "other.txt"]),
+            InputSelection::Single(InputSource::File("other.txt".into()))
+        );
+        assert_eq!(
+            parse(&["05", "--input-name", "stress"]),
+            InputSelection::Single(InputSource::Named("stress".into()))
+        );
+        assert_eq!(parse(&["05", "--all-inputs"]), InputSelection::All);
+    }
+
+    #[test]
+    fn rejects_names_outside_of_inputs() {
+        assert_eq!(
+            InputSource::named("stress").unwrap(),
+            InputSource::Named("stress".into())
+        );
+
+        for name in ["../../secret", "a/b", "a\\b", "..", ""] {
+            assert!(InputSource::named(name).is_err(), "{name:?}");
+        }
+
+        let error = InputSource::Named("../../secret".into())
+            .read(day!(1))
+            .unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn round_trips_through_args() {
+        for selection in [
+            InputSelection::Default,
+            InputSelection::All,
+            InputSelection::Single(InputSource::Stdin),
+            InputSelection::Single(InputSource::File("other.txt".into())),
+            InputSelection::Single(InputSource::Named("stress".into())),
+        ] {
+            let mut args = vec!["05".to_string()];
+            args.extend(selection.to_args());
+            assert_eq!(InputSelection::parse(&args), selection);
+        }
+    }
+}
@@ -0,0 +1,224 @@
+/// Module behind the [`dbg_log!`](crate::dbg_log) macro, which solutions use for debug output instead of `println!`.
+/// Messages are written to stderr, so that they neither end up in the answer line nor in the output that `all`
+/// parses. They are only shown with `solve --verbose`, and never during benchmark iterations.
+use std::{
+    env,
+    fmt::{self, Display},
+    io::{stdout, Write},
+    str::FromStr,
+    sync::atomic::{AtomicBool, AtomicU8, Ordering},
+};
+
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+/// Logs a message, formatted like `println!`, at the debug level unless a level is given.
+///
+/// ```ignore
+/// dbg_log!("visiting {node}");
+/// dbg_log!(info: "{} seeds", seeds.len());
+/// // only the first time this line is reached, e.g. inside of a loop.
+/// dbg_log!(once, trace: "first range: {range:?}");
+/// ```
+///
+/// The arguments are only evaluated if the message is shown, see [`template::log`](crate::template::log).
+#[macro_export]
+macro_rules! dbg_log {
+    (@log $level:ident, $($arg:tt)+) => {{
+        if $crate::template::log::is_enabled($crate::template::log::Level::$level) {
+            $crate::template::log::write($crate::template::log::Level::$level, format_args!($($arg)+));
+        }
+    }};
+    (@once $level:ident, $($arg:tt)+) => {{
+        static LOGGED: ::std::sync::atomic::AtomicBool = ::std::sync::atomic::AtomicBool::new(false);
+        if $crate::template::log::is_enabled($crate::template::log::Level::$level)
+            && !LOGGED.swap(true, ::std::sync::atomic::Ordering::Relaxed)
+        {
+            $crate::template::log::write($crate::template::log::Level::$level, format_args!($($arg)+));
+        }
+    }};
+    (once, error: $($arg:tt)+) => { $crate::dbg_log!(@once Error, $($arg)+) };
+    (once, warn: $($arg:tt)+) => { $crate::dbg_log!(@once Warn, $($arg)+) };
+    (once, info: $($arg:tt)+) => { $crate::dbg_log!(@once Info, $($arg)+) };
+    (once, debug: $($arg:tt)+) => { $crate::dbg_log!(@once Debug, $($arg)+) };
+    (once, trace: $($arg:tt)+) => { $crate::dbg_log!(@once Trace, $($arg)+) };
+    (once, $($arg:tt)+) => { $crate::dbg_log!(@once Debug, $($arg)+) };
+    (error: $($arg:tt)+) => { $crate::dbg_log!(@log Error, $($arg)+) };
+    (warn: $($arg:tt)+) => { $crate::dbg_log!(@log Warn, $($arg)+) };
+    (info: $($arg:tt)+) => { $crate::dbg_log!(@log Info, $($arg)+) };
+    (debug: $($arg:tt)+) => { $crate::dbg_log!(@log Debug, $($arg)+) };
+    (trace: $($arg:tt)+) => { $crate::dbg_log!(@log Trace, $($arg)+) };
+    ($($arg:tt)+) => { $crate::dbg_log!(@log Debug, $($arg)+) };
+}
+
+/// How important a message is. A level shows its own messages and those of every more important level.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Error = 1,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl Level {
+    const ALL: [Level; 5] = [
+        Level::Error,
+        Level::Warn,
+        Level::Info,
+        Level::Debug,
+        Level::Trace,
+    ];
+
+    fn from_u8(value: u8) -> Option<Self> {
+        Self::ALL.into_iter().find(|level| *level as u8 == value)
+    }
+}
+
+impl Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Level::Error => "error",
+            Level::Warn => "warn",
+            Level::Info => "info",
+            Level::Debug => "debug",
+            Level::Trace => "trace",
+        })
+    }
+}
+
+impl FromStr for Level {
+    type Err = LevelFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|level| level.to_string() == s)
+            .ok_or(LevelFromStrError)
+    }
+}
+
+/// An error which can be returned when parsing a [`Level`].
+#[derive(Debug)]
+pub struct LevelFromStrError;
+
+impl std::error::Error for LevelFromStrError {}
+
+impl Display for LevelFromStrError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(
+            "expecting a log level of \"error\", \"warn\", \"info\", \"debug\" or \"trace\"",
+        )
+    }
+}
+
+/// The most detailed level that is shown, `OFF` if logging is disabled and `UNSET` before [`level`] first read it.
+static LEVEL: AtomicU8 = AtomicU8::new(UNSET);
+const OFF: u8 = 0;
+const UNSET: u8 = u8::MAX;
+
+/// Set while the runner benchmarks a part, see [`suppressed`].
+static SUPPRESSED: AtomicBool = AtomicBool::new(false);
+
+/// The level passed to the current process with `--log-level <level>`, or [`Level::Debug`] for `--verbose`.
+/// Logging is disabled without either flag.
+#[must_use]
+pub fn level() -> Option<Level> {
+    match LEVEL.load(Ordering::Relaxed) {
+        UNSET => {
+            let level = level_from_args();
+            set_level(level);
+            level
+        }
+        value => Level::from_u8(value),
+    }
+}
+
+/// Override the level that was passed to the current process.
+pub fn set_level(level: Option<Level>) {
+    LEVEL.store(level.map_or(OFF, |level| level as u8), Ordering::Relaxed);
+}
+
+fn level_from_args() -> Option<Level> {
+    let args: Vec<String> = env::args().collect();
+
+    args.iter()
+        .position(|x| x == "--log-level")
+        .and_then(|i| args.get(i + 1))
+        .and_then(|x| x.parse().ok())
+        .or_else(|| {
+            args.iter()
+                .any(|x| x == "--verbose")
+                .then_some(Level::Debug)
+        })
+}
+
+/// Whether messages of `level` are shown right now. Used by the [`dbg_log!`](crate::dbg_log) macro.
+#[must_use]
+pub fn is_enabled(level: Level) -> bool {
+    !SUPPRESSED.load(Ordering::Relaxed) && self::level().is_some_and(|max| level <= max)
+}
+
+/// Write a message to stderr. Used by the [`dbg_log!`](crate::dbg_log) macro.
+pub fn write(level: Level, message: fmt::Arguments) {
+    // the runner may have printed part of a line, which should not be interleaved with the message.
+    let _ = stdout().flush();
+    eprintln!("{ANSI_ITALIC}{ANSI_BOLD}{level}{ANSI_RESET}{ANSI_ITALIC}: {message}{ANSI_RESET}");
+}
+
+/// Call `func` without showing any messages, e.g. while benchmarking. Applies to all threads.
+/// Messages are shown again once `func` returns or panics.
+pub fn suppressed<T>(func: impl FnOnce() -> T) -> T {
+    /// Restores the previous state when dropped, which also happens while a panic unwinds.
+    struct Guard {
+        was_suppressed: bool,
+    }
+
+    impl Drop for Guard {
+        fn drop(&mut self) {
+            SUPPRESSED.store(self.was_suppressed, Ordering::Relaxed);
+        }
+    }
+
+    let _guard = Guard {
+        was_suppressed: SUPPRESSED.swap(true, Ordering::Relaxed),
+    };
+    func()
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{is_enabled, set_level, suppressed, Level};
+
+    #[test]
+    fn parses_levels() {
+        assert_eq!("info".parse::<Level>().unwrap(), Level::Info);
+        assert_eq!("trace".parse::<Level>().unwrap(), Level::Trace);
+        assert!("verbose".parse::<Level>().is_err());
+    }
+
+    #[test]
+    fn filters_by_level() {
+        set_level(Some(Level::Info));
+        assert!(is_enabled(Level::Error));
+        assert!(is_enabled(Level::Info));
+        assert!(!is_enabled(Level::Debug));
+
+        // messages are never shown while benchmarking.
+        assert!(!suppressed(|| is_enabled(Level::Error)));
+        assert!(is_enabled(Level::Error));
+
+        // a benchmark that panics does not silence the remaining days.
+        let _ = std::panic::catch_unwind(|| suppressed(|| panic!("benchmark failed")));
+        assert!(is_enabled(Level::Error));
+
+        set_level(None);
+        assert!(!is_enabled(Level::Error));
+
+        // arguments are only evaluated if the message is shown.
+        fn expensive() -> u32 {
+            panic!("evaluated although logging is disabled")
+        }
+        crate::dbg_log!("{}", expensive());
+        crate::dbg_log!(once, trace: "{}", expensive());
+    }
+}
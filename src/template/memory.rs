@@ -0,0 +1,178 @@
+/// Module that measures the memory a solution part allocates.
+/// With the `memory` feature enabled, a counting global allocator is installed that wraps the system allocator.
+/// Without it, nothing is measured and [`measure`] compiles down to a plain function call.
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+
+/// Allocations made while a part ran.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MemoryStats {
+    /// The largest amount of memory held at once, on top of what was allocated before the part started.
+    pub peak_bytes: usize,
+    /// The sum of all allocations, including memory that was freed again.
+    pub total_bytes: usize,
+    pub allocations: usize,
+}
+
+impl Display for MemoryStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "peak {} · {} in {} allocations",
+            format_bytes(self.peak_bytes),
+            format_bytes(self.total_bytes),
+            self.allocations
+        )
+    }
+}
+
+/// Format a byte count with binary prefixes, e.g. `1.5 KiB`.
+#[must_use]
+pub fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+/// Call `func`, recording the allocations made until it returns.
+/// Allocations of other threads are counted as well, so parts should be measured one at a time.
+/// Returns no stats if the `memory` feature is disabled.
+pub fn measure<T>(func: impl FnOnce() -> T) -> (T, Option<MemoryStats>) {
+    #[cfg(feature = "memory")]
+    {
+        let baseline = allocator::reset();
+        let result = func();
+        (result, Some(allocator::snapshot(baseline)))
+    }
+
+    #[cfg(not(feature = "memory"))]
+    {
+        (func(), None)
+    }
+}
+
+#[cfg(feature = "memory")]
+mod allocator {
+    use super::MemoryStats;
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::sync::atomic::{AtomicUsize, Ordering::Relaxed};
+
+    static CURRENT: AtomicUsize = AtomicUsize::new(0);
+    static PEAK: AtomicUsize = AtomicUsize::new(0);
+    static TOTAL: AtomicUsize = AtomicUsize::new(0);
+    static COUNT: AtomicUsize = AtomicUsize::new(0);
+
+    struct CountingAllocator;
+
+    #[global_allocator]
+    static GLOBAL: CountingAllocator = CountingAllocator;
+
+    fn record(size: usize) {
+        let current = CURRENT.fetch_add(size, Relaxed) + size;
+        PEAK.fetch_max(current, Relaxed);
+        TOTAL.fetch_add(size, Relaxed);
+        COUNT.fetch_add(1, Relaxed);
+    }
+
+    unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc(layout);
+            if !ptr.is_null() {
+                record(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc_zeroed(layout);
+            if !ptr.is_null() {
+                record(layout.size());
+            }
+            ptr
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout);
+            CURRENT.fetch_sub(layout.size(), Relaxed);
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            let new_ptr = System.realloc(ptr, layout, new_size);
+            if !new_ptr.is_null() {
+                // a reallocation counts as a new allocation of the new size.
+                CURRENT.fetch_sub(layout.size(), Relaxed);
+                record(new_size);
+            }
+            new_ptr
+        }
+    }
+
+    /// Start a new measurement, returning the memory that is currently held.
+    pub fn reset() -> usize {
+        let baseline = CURRENT.load(Relaxed);
+        PEAK.store(baseline, Relaxed);
+        TOTAL.store(0, Relaxed);
+        COUNT.store(0, Relaxed);
+        baseline
+    }
+
+    pub fn snapshot(baseline: usize) -> MemoryStats {
+        MemoryStats {
+            peak_bytes: PEAK.load(Relaxed).saturating_sub(baseline),
+            total_bytes: TOTAL.load(Relaxed),
+            allocations: COUNT.load(Relaxed),
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_bytes, measure, MemoryStats};
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+    }
+
+    #[test]
+    fn formats_stats() {
+        let stats = MemoryStats {
+            peak_bytes: 2048,
+            total_bytes: 4096,
+            allocations: 3,
+        };
+        assert_eq!(stats.to_string(), "peak 2.0 KiB · 4.0 KiB in 3 allocations");
+    }
+
+    #[test]
+    fn measures_allocations() {
+        let (len, stats) = measure(|| vec![0_u8; 4096].len());
+        assert_eq!(len, 4096);
+
+        if cfg!(feature = "memory") {
+            let stats = stats.unwrap();
+            assert!(stats.peak_bytes >= 4096);
+            assert!(stats.total_bytes >= 4096);
+            assert!(stats.allocations >= 1);
+        } else {
+            assert!(stats.is_none());
+        }
+    }
+}
@@ -1,22 +1,50 @@
 use crate::Day;
-use std::{env, fs};
+use std::{env, fs, io};
 
+pub mod answers;
 pub mod aoc_cli;
+pub mod aoc_client;
+pub mod baseline;
+pub mod bench_history;
 pub mod commands;
+pub mod examples;
+pub mod exit_code;
+pub mod harness;
+pub mod inputs;
+pub mod log;
+pub mod memory;
+pub mod parse_error;
 pub mod readme_benchmarks;
+pub mod registry;
 pub mod runner;
+pub mod spans;
+pub mod stats;
+pub mod submissions;
+pub mod visualize;
+pub mod watch;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
+pub const ANSI_CLEAR: &str = "\x1b[2J\x1b[H";
+
+/// The year that puzzles are solved for, set by `AOC_YEAR` in `.cargo/config.toml`.
+#[must_use]
+pub fn aoc_year() -> Option<u16> {
+    env::var("AOC_YEAR").ok().and_then(|year| year.parse().ok())
+}
 
 /// Helper function that reads a text file to a string.
 #[must_use]
 pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
+    try_read_file(folder, day).expect("could not open input file")
+}
+
+/// Helper function that reads a text file to a string, returning an error if it cannot be read.
+pub fn try_read_file(folder: &str, day: Day) -> io::Result<String> {
+    let cwd = env::current_dir()?;
     let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    fs::read_to_string(filepath)
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
@@ -31,18 +59,108 @@ pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
     f.expect("could not open input file")
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Creates the constant `DAY`, registers the solution and sets up the input and runner for each part.
+///
+/// An optional parse function can be passed as second argument, e.g. `solution!(5, parse)`.
+/// It is called once, its output is passed by reference to both parts, and it is timed separately.
+/// As parts run on their own threads, the parsed type has to be `Send + Sync`.
+/// If parsing can fail, return a `Result` from the parse function and append `?`, e.g. `solution!(5, parse?)`.
+///
+/// Parts return either an `Option`, where `None` marks a part that is not solved yet, or a `Result`, see
+/// [`runner::IntoAnswer`].
 #[macro_export]
 macro_rules! solution {
     ($day:expr) => {
+        $crate::solution!(@solution $day, |input| {
+            use advent_of_code::template::runner::*;
+            use std::sync::Arc;
+            vec![
+                run_part(|input: Arc<str>| part_one(&input), input.clone(), DAY, 1),
+                run_part(|input: Arc<str>| part_two(&input), input, DAY, 2),
+            ]
+        }, |input, bencher| {
+            $crate::solution!(@bench_parts input, bencher);
+        });
+    };
+    ($day:expr, $parse:ident) => {
+        $crate::solution!(@solution $day, |input| {
+            use advent_of_code::template::runner::*;
+            use std::sync::Arc;
+            let (parsed, parse_result) = run_parse(
+                |input: Arc<str>| Ok::<_, std::convert::Infallible>($parse(&input)),
+                input,
+                DAY,
+            );
+            $crate::solution!(@parts parsed, parse_result)
+        }, |input, bencher| {
+            use std::hint::black_box;
+            bencher.bench(advent_of_code::template::runner::PARSE_PART, &|| {
+                black_box($parse(black_box(input)));
+            });
+            let parsed = $parse(input);
+            $crate::solution!(@bench_parts (&parsed), bencher);
+        });
+    };
+    ($day:expr, $parse:ident?) => {
+        $crate::solution!(@solution $day, |input| {
+            use advent_of_code::template::runner::*;
+            use std::sync::Arc;
+            let (parsed, parse_result) = run_parse(|input: Arc<str>| $parse(&input), input, DAY);
+            $crate::solution!(@parts parsed, parse_result)
+        }, |input, bencher| {
+            use std::hint::black_box;
+            let Some(parsed) = advent_of_code::template::harness::parsed(bencher, $parse(input)) else {
+                return;
+            };
+            bencher.bench(advent_of_code::template::runner::PARSE_PART, &|| {
+                let _ = black_box($parse(black_box(input)));
+            });
+            $crate::solution!(@bench_parts (&parsed), bencher);
+        });
+    };
+    (@parts $parsed:ident, $parse_result:ident) => {{
+        let Some(parsed) = $parsed else {
+            return vec![$parse_result];
+        };
+        let parsed = Arc::new(parsed);
+        vec![
+            $parse_result,
+            run_part(|parsed: Arc<_>| part_one(&parsed), parsed.clone(), DAY, 1),
+            run_part(|parsed: Arc<_>| part_two(&parsed), parsed, DAY, 2),
+        ]
+    }};
+    (@bench_parts $input:expr, $bencher:ident) => {{
+        use std::hint::black_box;
+        let input = $input;
+        $bencher.bench(1, &|| {
+            let _ = black_box(part_one(black_box(input)));
+        });
+        $bencher.bench(2, &|| {
+            let _ = black_box(part_two(black_box(input)));
+        });
+    }};
+    (@solution $day:expr, $run:expr, $bench:expr) => {
         /// The current day.
         const DAY: advent_of_code::Day = advent_of_code::day!($day);
 
+        /// The registry entry of the current day, see [`advent_of_code::template::registry`].
+        pub const SOLUTION: advent_of_code::template::registry::Solution =
+            advent_of_code::template::registry::Solution {
+                day: DAY,
+                run: $run,
+                bench: $bench,
+            };
+
         fn main() {
-            use advent_of_code::template::runner::*;
-            let input = advent_of_code::template::read_file("inputs", DAY);
-            run_part(part_one, &input, DAY, 1);
-            run_part(part_two, &input, DAY, 2);
+            use advent_of_code::template::{
+                exit_code::ExitCode, inputs::InputSelection, runner::run_inputs,
+            };
+            let run = run_inputs(&SOLUTION, &InputSelection::from_args());
+
+            match run.exit_code() {
+                ExitCode::Success => {}
+                code => code.exit(),
+            }
         }
     };
 }
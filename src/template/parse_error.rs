@@ -0,0 +1,193 @@
+/// Module with the error type that puzzle input parsers share.
+/// A [`ParseError`] remembers where its offending text lives in memory. As long as parsers create it from a slice
+/// of the input, the runner can find the line and column it came from and print an excerpt of the input, without
+/// parsers having to keep track of positions themselves.
+use std::{error::Error, fmt::Display, str::FromStr};
+
+/// A 1-based position in the input, columns are counted in characters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Position {
+    pub line: usize,
+    pub column: usize,
+}
+
+/// An error which can be returned when parsing a puzzle input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// The text that could not be parsed. Empty if something is missing.
+    pub text: String,
+    /// A description of the expected format, e.g. `"a number"`.
+    pub expected: String,
+    /// The position of `text` in the input, once known, see [`ParseError::located`].
+    pub position: Option<Position>,
+    /// Address of the offending text, used to find its position in the input.
+    address: usize,
+}
+
+impl ParseError {
+    /// Create an error for `text`, which should be a slice of the input for its position to be found.
+    #[must_use]
+    pub fn new(text: &str, expected: impl Into<String>) -> Self {
+        Self {
+            text: text.into(),
+            expected: expected.into(),
+            position: None,
+            address: text.as_ptr() as usize,
+        }
+    }
+
+    /// Create an error for something that is missing after `text`, e.g. a section that should follow it.
+    #[must_use]
+    pub fn missing(after: &str, expected: impl Into<String>) -> Self {
+        Self::new(&after[after.len()..], expected)
+    }
+
+    /// Find the position of the offending text in `input`.
+    /// Returns `None` if the text is not a slice of `input`, e.g. because it was copied while parsing.
+    #[must_use]
+    pub fn position_in(&self, input: &str) -> Option<Position> {
+        let offset = self.address.checked_sub(input.as_ptr() as usize)?;
+
+        if offset > input.len() || !input.is_char_boundary(offset) {
+            return None;
+        }
+
+        let before = &input[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+
+        Some(Position {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+        })
+    }
+
+    /// Fill in the position of the offending text in `input`, if it is not known yet.
+    #[must_use]
+    pub fn located(mut self, input: &str) -> Self {
+        if self.position.is_none() {
+            self.position = self.position_in(input);
+        }
+        self
+    }
+
+    /// The line of `input` that contains the error, with carets under the offending text:
+    ///
+    /// ```text
+    ///   |
+    /// 3 | Card 3: 1 x 3 | 4
+    ///   |           ^
+    /// ```
+    #[must_use]
+    pub fn excerpt(&self, input: &str) -> Option<String> {
+        let position = self.position?;
+        let line = input.lines().nth(position.line - 1).unwrap_or_default();
+        let gutter = " ".repeat(position.line.to_string().len());
+        let width = self.text.lines().next().map_or(0, |x| x.chars().count());
+
+        Some(format!(
+            "{gutter} |\n{} | {line}\n{gutter} | {}{}",
+            position.line,
+            " ".repeat(position.column - 1),
+            "^".repeat(width.max(1))
+        ))
+    }
+
+    /// The offending text as shown in messages, shortened to its first line.
+    fn found(&self) -> String {
+        const MAX_CHARS: usize = 40;
+
+        let Some(first_line) = self.text.lines().next() else {
+            return "nothing".into();
+        };
+
+        if first_line.chars().count() > MAX_CHARS || self.text.contains('\n') {
+            let shortened: String = first_line.chars().take(MAX_CHARS).collect();
+            format!("\"{shortened}…\"")
+        } else {
+            format!("\"{first_line}\"")
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(position) = self.position {
+            write!(f, "line {}, column {}: ", position.line, position.column)?;
+        }
+
+        write!(f, "expected {}, found {}", self.expected, self.found())
+    }
+}
+
+/// Parse a number, e.g. with `parse_number::<u32>(text)`.
+pub fn parse_number<T: FromStr>(text: &str) -> Result<T, ParseError> {
+    text.parse().map_err(|_| ParseError::new(text, "a number"))
+}
+
+/// Split `text` at the first occurrence of `delimiter`.
+pub fn split_once<'a>(text: &'a str, delimiter: &str) -> Result<(&'a str, &'a str), ParseError> {
+    text.split_once(delimiter)
+        .ok_or_else(|| ParseError::new(text, format!("text separated by {delimiter:?}")))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_number, split_once, ParseError, Position};
+
+    const INPUT: &str = "Card 1: 1 2 | 3\nCard 2: 4 x | 6\n";
+
+    #[test]
+    fn locates_slices_of_input() {
+        let error = parse_number::<u32>(&INPUT[26..27]).unwrap_err();
+        assert_eq!(
+            error.position_in(INPUT),
+            Some(Position {
+                line: 2,
+                column: 11
+            })
+        );
+        assert_eq!(
+            error.located(INPUT).to_string(),
+            "line 2, column 11: expected a number, found \"x\""
+        );
+    }
+
+    #[test]
+    fn does_not_locate_copies() {
+        let copy = INPUT[26..27].to_string();
+        let error = ParseError::new(&copy, "a number");
+        assert_eq!(error.position_in(INPUT), None);
+        assert_eq!(error.to_string(), "expected a number, found \"x\"");
+    }
+
+    #[test]
+    fn prints_excerpt() {
+        let error = ParseError::new(&INPUT[16..22], "a card").located(INPUT);
+        assert_eq!(
+            error.excerpt(INPUT).unwrap(),
+            "  |\n2 | Card 2: 4 x | 6\n  | ^^^^^^"
+        );
+    }
+
+    #[test]
+    fn reports_missing_text() {
+        let line = INPUT.lines().next().unwrap();
+        let error = split_once(line, " -> ").unwrap_err();
+        assert_eq!(error.expected, "text separated by \" -> \"");
+
+        let error = ParseError::missing(INPUT.trim_end(), "a third card").located(INPUT);
+        assert_eq!(
+            error.position,
+            Some(Position {
+                line: 2,
+                column: 16
+            })
+        );
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 16: expected a third card, found nothing"
+        );
+    }
+}
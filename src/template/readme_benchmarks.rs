@@ -2,6 +2,7 @@
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
 use std::{fs, io};
 
+use crate::template::{memory::MemoryStats, stats::BenchStats};
 use crate::Day;
 
 static MARKER: &str = "<!--- benchmarking table --->";
@@ -21,11 +22,63 @@ impl From<std::io::Error> for Error {
 #[derive(Clone)]
 pub struct Timings {
     pub day: Day,
-    pub part_1: Option<String>,
-    pub part_2: Option<String>,
+    /// Timings of the shared parse phase, for solutions that declare one.
+    pub parse: Option<PartTimings>,
+    pub part_1: Option<PartTimings>,
+    pub part_2: Option<PartTimings>,
     pub total_nanos: f64,
 }
 
+/// The formatted mean execution time of a part, with the statistics it was derived from if available.
+#[derive(Clone)]
+pub struct PartTimings {
+    pub mean: String,
+    pub stats: Option<BenchStats>,
+    /// Allocations of the part, if it was run with `--memory`.
+    pub memory: Option<MemoryStats>,
+    /// Whether the part panicked, timed out or returned an error, as opposed to not being implemented.
+    pub failed: bool,
+}
+
+impl PartTimings {
+    #[must_use]
+    pub fn failed() -> Self {
+        Self {
+            mean: String::new(),
+            stats: None,
+            memory: None,
+            failed: true,
+        }
+    }
+
+    fn to_cell(&self) -> String {
+        if self.failed {
+            return "`✖ failed`".into();
+        }
+
+        let time = match &self.stats {
+            Some(stats) => format!("`{} ± {:.1?}`", self.mean, stats.std_dev),
+            None => format!("`{}`", self.mean),
+        };
+
+        match &self.memory {
+            Some(memory) => format!("{time}<br>`{memory}`"),
+            None => time,
+        }
+    }
+}
+
+impl From<&str> for PartTimings {
+    fn from(mean: &str) -> Self {
+        Self {
+            mean: mean.into(),
+            stats: None,
+            memory: None,
+            failed: false,
+        }
+    }
+}
+
 pub struct TablePosition {
     pos_start: usize,
     pos_end: usize,
@@ -60,23 +113,36 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
 
 fn construct_table(prefix: &str, timings: Vec<Timings>, total_millis: f64) -> String {
     let header = format!("{prefix} Benchmarks");
+    let has_parse = timings.iter().any(|t| t.parse.is_some());
 
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
+    let mut lines: Vec<String> = vec![MARKER.into(), header, String::new()];
+
+    if has_parse {
+        lines.push("| Day | Parse | Part 1 | Part 2 |".into());
+        lines.push("| :---: | :---: | :---: | :---:  |".into());
+    } else {
+        lines.push("| Day | Part 1 | Part 2 |".into());
+        lines.push("| :---: | :---: | :---:  |".into());
+    }
 
     for timing in timings {
         let path = get_path_for_bin(timing.day);
+        let parse = if has_parse {
+            format!(
+                " {} |",
+                timing.parse.map_or_else(|| "`-`".into(), |t| t.to_cell())
+            )
+        } else {
+            String::new()
+        };
+
         lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
+            "| [Day {}]({}) |{} {} | {} |",
             timing.day.into_inner(),
             path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
+            parse,
+            timing.part_1.map_or_else(|| "`-`".into(), |t| t.to_cell()),
+            timing.part_2.map_or_else(|| "`-`".into(), |t| t.to_cell())
         ));
     }
 
@@ -104,25 +170,30 @@ pub fn update(timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, Timings, MARKER};
+    use super::{update_content, PartTimings, Timings, MARKER};
     use crate::day;
+    use crate::template::{memory::MemoryStats, stats::BenchStats};
+    use std::time::Duration;
 
     fn get_mock_timings() -> Vec<Timings> {
         vec![
             Timings {
                 day: day!(1),
+                parse: None,
                 part_1: Some("10ms".into()),
                 part_2: Some("20ms".into()),
                 total_nanos: 3e+10,
             },
             Timings {
                 day: day!(2),
+                parse: None,
                 part_1: Some("30ms".into()),
                 part_2: Some("40ms".into()),
                 total_nanos: 7e+10,
             },
             Timings {
                 day: day!(4),
+                parse: None,
                 part_1: Some("40ms".into()),
                 part_2: Some("50ms".into()),
                 total_nanos: 9e+10,
@@ -183,4 +254,92 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_benchmarks_with_stats() {
+        let mut s = format!("{}{}", MARKER, MARKER);
+        let ms = Duration::from_millis;
+        let timings = vec![Timings {
+            day: day!(1),
+            parse: None,
+            part_1: Some(PartTimings {
+                mean: "10.0ms".into(),
+                stats: Some(BenchStats {
+                    mean: ms(10),
+                    median: ms(9),
+                    std_dev: Duration::from_micros(1500),
+                    min: ms(8),
+                    max: ms(14),
+                    p95: ms(13),
+                    p99: ms(14),
+                    outliers: 0,
+                }),
+                memory: None,
+                failed: false,
+            }),
+            part_2: None,
+            total_nanos: 1e+7,
+        }];
+        update_content(&mut s, timings, 10.0).unwrap();
+        assert!(s.contains("| [Day 1](./src/bin/01.rs) | `10.0ms ± 1.5ms` | `-` |"));
+    }
+
+    #[test]
+    fn format_benchmarks_with_parse() {
+        let mut s = format!("{}{}", MARKER, MARKER);
+        let mut timings = get_mock_timings();
+        timings[0].parse = Some("5ms".into());
+        update_content(&mut s, timings, 190.0).unwrap();
+        let expected = [
+            "<!--- benchmarking table --->",
+            "## Benchmarks",
+            "",
+            "| Day | Parse | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: | :---:  |",
+            "| [Day 1](./src/bin/01.rs) | `5ms` | `10ms` | `20ms` |",
+            "| [Day 2](./src/bin/02.rs) | `-` | `30ms` | `40ms` |",
+            "| [Day 4](./src/bin/04.rs) | `-` | `40ms` | `50ms` |",
+            "",
+            "**Total: 190.00ms**",
+            "<!--- benchmarking table --->",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn format_benchmarks_with_memory() {
+        let mut s = format!("{}{}", MARKER, MARKER);
+        let timings = vec![Timings {
+            day: day!(1),
+            parse: None,
+            part_1: Some(PartTimings {
+                mean: "10.0ms".into(),
+                stats: None,
+                memory: Some(MemoryStats {
+                    peak_bytes: 3 * 1024 * 1024,
+                    total_bytes: 5 * 1024 * 1024,
+                    allocations: 42,
+                }),
+                failed: false,
+            }),
+            part_2: Some("20ms".into()),
+            total_nanos: 3e+7,
+        }];
+        update_content(&mut s, timings, 30.0).unwrap();
+        assert!(s.contains(
+            "| [Day 1](./src/bin/01.rs) | `10.0ms`<br>`peak 3.0 MiB · 5.0 MiB in 42 allocations` | `20ms` |"
+        ));
+    }
+
+    #[test]
+    fn format_benchmarks_with_failures() {
+        let mut s = format!("{}{}", MARKER, MARKER);
+        let mut timings = get_mock_timings();
+        timings[0].part_2 = Some(PartTimings::failed());
+        timings[1].part_2 = None;
+        update_content(&mut s, timings, 190.0).unwrap();
+        assert!(s.contains("| [Day 1](./src/bin/01.rs) | `10ms` | `✖ failed` |"));
+        assert!(s.contains("| [Day 2](./src/bin/02.rs) | `30ms` | `-` |"));
+    }
 }
@@ -0,0 +1,55 @@
+/// Registry of solutions that can be invoked in-process.
+/// The `solution!` macro creates a [`Solution`] for each day, and the build script collects all of them
+/// into the `SOLUTIONS` slice of the main binary, so that `cargo all` does not need to spawn a binary per day.
+use std::sync::Arc;
+
+use crate::template::{harness::Bencher, runner::PartResult};
+use crate::Day;
+
+/// A solution registered by the `solution!` macro.
+pub struct Solution {
+    pub day: Day,
+    /// Runs both parts of the solution against an input and returns their results.
+    pub run: fn(Arc<str>) -> Vec<PartResult>,
+    /// Passes the parse phase and both parts to a [`Bencher`], see `cargo bench`.
+    pub bench: fn(&str, &mut dyn Bencher),
+}
+
+/// Looks up the solution for a given day.
+#[must_use]
+pub fn find(solutions: &[Solution], day: Day) -> Option<&Solution> {
+    solutions.iter().find(|solution| solution.day == day)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{find, Solution};
+    use crate::day;
+    use crate::template::harness::Bencher;
+    use std::sync::Arc;
+
+    fn run_nothing(_: Arc<str>) -> Vec<crate::template::runner::PartResult> {
+        vec![]
+    }
+
+    fn bench_nothing(_: &str, _: &mut dyn Bencher) {}
+
+    #[test]
+    fn finds_registered_days() {
+        let solutions = [
+            Solution {
+                day: day!(1),
+                run: run_nothing,
+                bench: bench_nothing,
+            },
+            Solution {
+                day: day!(5),
+                run: run_nothing,
+                bench: bench_nothing,
+            },
+        ];
+
+        assert_eq!(find(&solutions, day!(5)).map(|s| s.day), Some(day!(5)));
+        assert!(find(&solutions, day!(2)).is_none());
+    }
+}
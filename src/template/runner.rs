@@ -1,63 +1,709 @@
 /// Encapsulates code that interacts with solution functions.
-use crate::template::{aoc_cli, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{
+    answers, aoc_cli,
+    aoc_client::{self, AocClient, Submission},
+    bench_history::timestamp,
+    exit_code::ExitCode,
+    inputs::{InputSelection, InputSource},
+    log,
+    memory::{self, MemoryStats},
+    parse_error::ParseError,
+    registry::Solution,
+    spans::{self, SpanTree},
+    stats::BenchStats,
+    submissions::{self, Verdict},
+    visualize::{self, Frames},
+    ANSI_ITALIC, ANSI_RESET,
+};
 use crate::Day;
+use serde::{Deserialize, Serialize};
+use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::error::Error;
 use std::fmt::Display;
+use std::hint::black_box;
 use std::io::{stdout, Write};
-use std::process::Output;
+use std::panic::{self, AssertUnwindSafe};
+use std::str::FromStr;
+use std::sync::{mpsc, Arc};
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{cmp, env, process, thread};
 
 use super::ANSI_BOLD;
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+/// The outcome of running a single part of a solution.
+/// In `--format json` mode, this is printed as one JSON record per line.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PartResult {
+    pub day: Day,
+    /// The part number, or [`PARSE_PART`] for the shared parse phase.
+    pub part: u8,
+    /// The name of the input, see [`InputSource::name`]. `None` for the default input.
+    pub input: Option<String>,
+    pub answer: Option<String>,
+    /// The accepted answer stored in `data/answers`, if known.
+    pub expected: Option<String>,
+    #[serde(rename = "duration_nanos", with = "duration_nanos")]
+    pub duration: Duration,
+    pub samples: u128,
+    /// Statistics over all samples, if the part was benchmarked with `--time`.
+    pub stats: Option<BenchStats>,
+    /// Allocations of the first run, if built with the `memory` feature.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub memory: Option<MemoryStats>,
+    /// Spans of the first run, if built with the `spans` feature, see [`spans`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub spans: Option<SpanTree>,
+    pub status: PartStatus,
+    /// Why the part failed, if it panicked, timed out or returned an error.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl PartResult {
+    /// An unsolved result of a part on the default input that took no time, to be completed with struct update syntax.
+    #[must_use]
+    pub fn new(day: Day, part: u8) -> Self {
+        Self {
+            day,
+            part,
+            input: None,
+            answer: None,
+            expected: None,
+            duration: Duration::ZERO,
+            samples: 1,
+            stats: None,
+            memory: None,
+            spans: None,
+            status: PartStatus::Unsolved,
+            error: None,
+        }
+    }
+}
+
+/// Whether a part produced an answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PartStatus {
+    Solved,
+    Unsolved,
+    /// The answer does not match the stored accepted answer.
+    Wrong,
+    /// The parse phase completed.
+    Parsed,
+    /// The part panicked, see [`PartResult::error`] for the panic message.
+    Panicked,
+    /// The part did not finish within the `--timeout`.
+    TimedOut,
+    /// The part returned an error, see [`PartResult::error`] for its message and sources.
+    Failed,
+}
+
+impl PartStatus {
+    /// Whether the part ran to completion, i.e. whether its duration is meaningful.
+    #[must_use]
+    pub fn has_timing(self) -> bool {
+        matches!(self, Self::Solved | Self::Wrong | Self::Parsed)
+    }
+
+    /// Whether the part panicked, timed out or returned an error.
+    #[must_use]
+    pub fn is_failure(self) -> bool {
+        matches!(self, Self::Panicked | Self::TimedOut | Self::Failed)
+    }
+}
+
+/// Conversion of what a solution part returns into its answer.
+/// Implemented for `Option<T>`, where `None` means that the part is not solved yet, and for `Result<T, E>`,
+/// where an error fails the part.
+pub trait IntoAnswer {
+    type Answer: Display;
+
+    fn into_answer(self) -> Result<Option<Self::Answer>, anyhow::Error>;
+}
+
+impl<T: Display> IntoAnswer for Option<T> {
+    type Answer = T;
+
+    fn into_answer(self) -> Result<Option<T>, anyhow::Error> {
+        Ok(self)
+    }
+}
+
+impl<T: Display, E: Into<anyhow::Error>> IntoAnswer for Result<T, E> {
+    type Answer = T;
+
+    fn into_answer(self) -> Result<Option<T>, anyhow::Error> {
+        self.map(Some).map_err(Into::into)
+    }
+}
+
+/// The part number that [`PartResult`]s of the parse phase are reported with.
+pub const PARSE_PART: u8 = 0;
+
+/// How the runner reports results, selected with `--format <text|json>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+impl FromStr for OutputFormat {
+    type Err = OutputFormatFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            _ => Err(OutputFormatFromStrError),
+        }
+    }
+}
+
+/// An error which can be returned when parsing an [`OutputFormat`].
+#[derive(Debug)]
+pub struct OutputFormatFromStrError;
+
+impl Error for OutputFormatFromStrError {}
+
+impl Display for OutputFormatFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting an output format of either \"text\" or \"json\"")
+    }
+}
+
+/// Read the `--format` flag passed to the current process, defaulting to text output.
+#[must_use]
+pub fn output_format() -> OutputFormat {
+    let args: Vec<String> = env::args().collect();
+
+    args.iter()
+        .position(|x| x == "--format")
+        .and_then(|i| args.get(i + 1))
+        .and_then(|x| x.parse().ok())
+        .unwrap_or_default()
+}
+
+/// Read the `--timeout <seconds>` flag passed to the current process. Parts may run indefinitely by default.
+#[must_use]
+pub fn part_timeout() -> Option<Duration> {
+    let args: Vec<String> = env::args().collect();
+
+    args.iter()
+        .position(|x| x == "--timeout")
+        .and_then(|i| args.get(i + 1))
+        .and_then(|x| x.parse().ok())
+        .and_then(|x| Duration::try_from_secs_f64(x).ok())
+}
+
+thread_local! {
+    /// The input that solutions on the current thread are running on, set by [`run_inputs`].
+    static CURRENT_INPUT: RefCell<InputSource> = const { RefCell::new(InputSource::Default) };
+    /// The text of [`CURRENT_INPUT`], used to point at the location of a [`ParseError`].
+    static CURRENT_TEXT: RefCell<Option<Arc<str>>> = const { RefCell::new(None) };
+    /// The most severe failure of the current [`run_inputs`] that is not part of its [`PartResult`]s.
+    static FAILURE: Cell<ExitCode> = const { Cell::new(ExitCode::Success) };
+}
+
+fn record_failure(code: ExitCode) {
+    FAILURE.with(|failure| failure.set(failure.get().max(code)));
+}
+
+/// The outcome of running a solution on its inputs, see [`run_inputs`].
+#[derive(Debug, Clone, Default)]
+pub struct SolutionRun {
+    pub results: Vec<PartResult>,
+    /// The most severe failure that is not part of `results`, like a missing input or a failed submission.
+    pub failure: ExitCode,
+}
+
+impl SolutionRun {
+    /// The code that a solution binary exits with after this run.
+    #[must_use]
+    pub fn exit_code(&self) -> ExitCode {
+        ExitCode::of_results(&self.results).max(self.failure)
+    }
+}
+
+/// Run a solution on every selected input. Results of inputs other than the default one are labeled with
+/// the input's name, and are neither checked against stored answers nor submitted.
+pub fn run_inputs(solution: &Solution, selection: &InputSelection) -> SolutionRun {
+    let format = output_format();
+    let mut results = vec![];
+    FAILURE.with(|failure| failure.set(ExitCode::Success));
+
+    for source in selection.resolve(solution.day) {
+        if let (Some(name), OutputFormat::Text) = (source.name(), format) {
+            println!("{ANSI_ITALIC}Input: {name}{ANSI_RESET}");
+        }
+
+        let input = match source.read(solution.day) {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!("could not open input file: {e}");
+                record_failure(ExitCode::Error);
+                continue;
+            }
+        };
+
+        let input: Arc<str> = input.into();
+        CURRENT_INPUT.with(|current| *current.borrow_mut() = source.clone());
+        CURRENT_TEXT.with(|current| *current.borrow_mut() = Some(input.clone()));
+        results.extend((solution.run)(input));
+        CURRENT_INPUT.with(|current| *current.borrow_mut() = InputSource::Default);
+        CURRENT_TEXT.with(|current| *current.borrow_mut() = None);
+    }
+
+    SolutionRun {
+        results,
+        failure: FAILURE.with(Cell::take),
+    }
+}
+
+/// Run a part of a solution. Each part is isolated: a panic or a run that exceeds the `--timeout` is reported
+/// as a failed [`PartResult`] instead of taking down the remaining parts.
+pub fn run_part<I, A, F>(func: F, input: I, day: Day, part: u8) -> PartResult
+where
+    I: Clone + Send + 'static,
+    A: IntoAnswer,
+    A::Answer: Send + 'static,
+    F: Fn(I) -> A + Send + Sync + 'static,
+{
+    let format = output_format();
     let part_str = format!("Part {part}");
+    let source = CURRENT_INPUT.with(|current| current.borrow().clone());
+    let func = move |input| func(input).into_answer();
+
+    let run = run_timed(func, input, &part_str, format, |result| {
+        if format == OutputFormat::Text {
+            print_result(result, &part_str, "", "");
+        }
+    });
+
+    let run = match run {
+        Ok(run) => run,
+        Err(failure) => {
+            let part_result = failure.into_result(day, part, source.name());
+            print_part_result(&part_result, format);
+            return part_result;
+        }
+    };
+
+    let answer = run.result.as_ref().map(ToString::to_string);
+    let expected = if source == InputSource::Default {
+        read_expected_answer(day, part)
+    } else {
+        None
+    };
+
+    let status = match (&answer, &expected) {
+        (None, _) => PartStatus::Unsolved,
+        (Some(answer), Some(expected)) if answer != expected => PartStatus::Wrong,
+        (Some(_), _) => PartStatus::Solved,
+    };
+
+    let part_result = PartResult {
+        day,
+        part,
+        input: source.name(),
+        answer,
+        expected,
+        duration: run.duration,
+        samples: run.samples,
+        stats: run.stats,
+        memory: run.memory,
+        spans: run.spans,
+        status,
+        error: None,
+    };
+
+    print_part_result(&part_result, format);
+    show_frames(run.frames.as_ref(), &format!("part{part}"), format);
+
+    if let (Some(result), InputSource::Default) = (run.result, source) {
+        match submit_result(result, day, part) {
+            Some(Ok(submission)) if !submission.is_correct() => {
+                record_failure(ExitCode::SubmissionFailed);
+            }
+            Some(Err(e)) => {
+                eprintln!("failed to submit answer: {e}");
+                record_failure(ExitCode::SubmissionFailed);
+            }
+            _ => {}
+        }
+    }
+
+    part_result
+}
+
+/// Run the optional parse phase of a solution. Its output is shared by both parts, and it is timed separately.
+/// Returns no output if the parse phase panicked, timed out or returned an error.
+pub fn run_parse<I, T, E, F>(func: F, input: I, day: Day) -> (Option<T>, PartResult)
+where
+    I: Clone + Send + 'static,
+    T: Send + 'static,
+    E: Into<anyhow::Error>,
+    F: Fn(I) -> Result<T, E> + Send + Sync + 'static,
+{
+    let format = output_format();
+    let input_name = CURRENT_INPUT.with(|current| current.borrow().name());
+    let func = move |input| func(input).map_err(Into::into);
+
+    let run = run_timed(func, input, "Parse", format, |_| {
+        if format == OutputFormat::Text {
+            print!("Parse:");
+        }
+    });
+
+    let (parsed, part_result) = match run {
+        Ok(run) => {
+            let part_result = PartResult {
+                day,
+                part: PARSE_PART,
+                input: input_name,
+                answer: None,
+                expected: None,
+                duration: run.duration,
+                samples: run.samples,
+                stats: run.stats,
+                memory: run.memory,
+                spans: run.spans,
+                status: PartStatus::Parsed,
+                error: None,
+            };
+            print_part_result(&part_result, format);
+            show_frames(run.frames.as_ref(), "parse", format);
+            (Some(run.result), part_result)
+        }
+        Err(failure) => {
+            let part_result = failure.into_result(day, PARSE_PART, input_name);
+            print_part_result(&part_result, format);
+            (None, part_result)
+        }
+    };
+
+    (parsed, part_result)
+}
+
+/// Print the final result of a part in the given format.
+pub fn print_part_result(result: &PartResult, format: OutputFormat) {
+    match format {
+        OutputFormat::Text if result.status.is_failure() => {
+            let label = match result.part {
+                PARSE_PART => "Parse".into(),
+                part => format!("Part {part}"),
+            };
+
+            print!("\r");
+            println!(
+                "{label}: {ANSI_BOLD}✖{ANSI_RESET} {}             ",
+                result.error.as_deref().unwrap_or_default()
+            );
+        }
+        OutputFormat::Text if result.status == PartStatus::Parsed => {
+            print!("\r");
+            println!(
+                "Parse:{}             ",
+                format_duration(&result.duration, result.samples)
+            );
+
+            if let Some(stats) = &result.stats {
+                println!("{}", format_stats(stats));
+            }
+
+            if let Some(memory) = &result.memory {
+                println!("  {ANSI_ITALIC}{memory}{ANSI_RESET}");
+            }
+
+            print_spans(result.spans.as_ref());
+        }
+        OutputFormat::Text => {
+            let check = match (&result.answer, &result.expected) {
+                (Some(answer), Some(expected)) if answer == expected => " ✓".into(),
+                (Some(_), Some(expected)) => format!(" ✗ (expected {expected})"),
+                _ => String::new(),
+            };
+
+            print_result(
+                &result.answer,
+                &format!("Part {}", result.part),
+                &check,
+                &format_duration(&result.duration, result.samples),
+            );
+
+            if let (Some(_), Some(stats)) = (&result.answer, &result.stats) {
+                println!("{}", format_stats(stats));
+            }
+
+            if let (Some(_), Some(memory)) = (&result.answer, &result.memory) {
+                println!("  {ANSI_ITALIC}{memory}{ANSI_RESET}");
+            }
+
+            print_spans(result.spans.as_ref());
+        }
+        OutputFormat::Json => println!("{}", serde_json::to_string(result).unwrap()),
+    }
+}
+
+/// Print the spans that a part recorded, if built with the `spans` feature, see [`spans`].
+fn print_spans(spans: Option<&SpanTree>) {
+    match spans {
+        Some(spans) if !spans.is_empty() => print!("{spans}"),
+        _ => {}
+    }
+}
+
+/// Replay or export the frames that a part recorded, if built with the `visualize` feature, see [`visualize`].
+fn show_frames(frames: Option<&Frames>, label: &str, format: OutputFormat) {
+    if let (Some(frames), OutputFormat::Text) = (frames, format) {
+        visualize::show(frames, label, &visualize::Options::from_args());
+    }
+}
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+/// Stack size of the threads that parts run on. Generous, so that recursive solutions do not overflow earlier than
+/// they would on the main thread.
+const PART_STACK_SIZE: usize = 64 * 1024 * 1024;
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+/// Why a part did not produce a result.
+#[derive(Debug, Clone, PartialEq)]
+enum Failure {
+    Panicked(String),
+    TimedOut(Duration),
+    /// The part returned an error, formatted with [`format_error`].
+    Errored(String),
+}
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+impl Display for Failure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Failure::Panicked(message) => write!(f, "panicked: {message}"),
+            Failure::TimedOut(timeout) => write!(f, "timed out after {timeout:.1?}"),
+            Failure::Errored(message) => write!(f, "failed: {message}"),
+        }
     }
 }
 
+impl Failure {
+    fn into_result(self, day: Day, part: u8, input: Option<String>) -> PartResult {
+        let (status, duration) = match self {
+            Failure::Panicked(_) => (PartStatus::Panicked, Duration::ZERO),
+            Failure::TimedOut(timeout) => (PartStatus::TimedOut, timeout),
+            Failure::Errored(_) => (PartStatus::Failed, Duration::ZERO),
+        };
+
+        PartResult {
+            input,
+            duration,
+            status,
+            error: Some(self.to_string()),
+            ..PartResult::new(day, part)
+        }
+    }
+}
+
+/// A successful run of a solution part.
+struct TimedRun<T> {
+    result: T,
+    duration: Duration,
+    samples: u128,
+    stats: Option<BenchStats>,
+    memory: Option<MemoryStats>,
+    /// Spans of the first run, if built with the `spans` feature.
+    spans: Option<SpanTree>,
+    /// Frames of the first run, if built with the `visualize` feature.
+    frames: Option<Frames>,
+}
+
+/// The first run of a solution part, see [`run_isolated`].
+#[derive(Debug)]
+struct IsolatedRun<T> {
+    result: T,
+    duration: Duration,
+    memory: Option<MemoryStats>,
+    spans: Option<SpanTree>,
+    frames: Option<Frames>,
+}
+
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
-fn run_timed<I: Clone, T>(
-    func: impl Fn(I) -> T,
+///
+/// The first execution is isolated, see [`run_isolated`], and its allocations, spans and frames are recorded.
+/// Benchmark iterations only start once it succeeded without returning an error.
+fn run_timed<I, T, F>(
+    func: F,
     input: I,
+    label: &str,
+    format: OutputFormat,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
-    let timer = Instant::now();
-    let result = func(input.clone());
-    let base_time = timer.elapsed();
+) -> Result<TimedRun<T>, Failure>
+where
+    I: Clone + Send + 'static,
+    T: Send + 'static,
+    F: Fn(I) -> Result<T, anyhow::Error> + Send + Sync + 'static,
+{
+    let func = Arc::new(func);
+    let first_run = run_isolated(&func, input.clone(), label, part_timeout())?;
+    let base_time = first_run.duration;
+    let result = first_run.result.map_err(|e| {
+        let text = CURRENT_TEXT.with(|current| current.borrow().clone());
+        Failure::Errored(format_error(&e, text.as_deref()))
+    })?;
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+    let (duration, samples, stats) = if std::env::args().any(|x| x == "--time") {
+        bench(&*func, input, format, &base_time)
     } else {
-        (base_time, 1)
+        (base_time, 1, None)
+    };
+
+    Ok(TimedRun {
+        result,
+        duration,
+        samples,
+        stats,
+        memory: first_run.memory,
+        spans: first_run.spans,
+        frames: first_run.frames,
+    })
+}
+
+/// Execute a function once on a separate thread, catching panics and giving up after `timeout`.
+/// A thread that timed out cannot be stopped. It is left running in the background until the process exits, which is
+/// why `all` runs the days after a timeout in child processes.
+fn run_isolated<I, T, F>(
+    func: &Arc<F>,
+    input: I,
+    label: &str,
+    timeout: Option<Duration>,
+) -> Result<IsolatedRun<T>, Failure>
+where
+    I: Send + 'static,
+    T: Send + 'static,
+    F: Fn(I) -> T + Send + Sync + 'static,
+{
+    let (sender, receiver) = mpsc::channel();
+    let func = Arc::clone(func);
+
+    thread::Builder::new()
+        .name(label.into())
+        .stack_size(PART_STACK_SIZE)
+        .spawn(move || {
+            let timer = Instant::now();
+            let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                visualize::collect(|| spans::collect(|| memory::measure(|| func(input))))
+            }));
+            let elapsed = timer.elapsed();
+            // the receiver is gone if the part timed out, in which case there is nobody left to report to.
+            let _ = sender.send(
+                result
+                    .map(|(((result, memory), spans), frames)| IsolatedRun {
+                        result,
+                        duration: elapsed,
+                        memory,
+                        spans,
+                        frames,
+                    })
+                    .map_err(|payload| Failure::Panicked(panic_message(&*payload))),
+            );
+        })
+        .expect("failed to spawn a thread for the solution");
+
+    let received = match timeout {
+        Some(timeout) => receiver.recv_timeout(timeout).map_err(|e| match e {
+            mpsc::RecvTimeoutError::Timeout => Failure::TimedOut(timeout),
+            mpsc::RecvTimeoutError::Disconnected => Failure::Panicked("thread exited".into()),
+        }),
+        None => receiver
+            .recv()
+            .map_err(|_| Failure::Panicked("thread exited".into())),
     };
 
-    (result, run.0, run.1)
+    received?
+}
+
+/// Format an error with the chain of errors that caused it, one per line.
+/// If one of them is a [`ParseError`] in `input`, its position is included along with an excerpt of the input.
+fn format_error(error: &anyhow::Error, input: Option<&str>) -> String {
+    let mut lines = vec![];
+    let mut excerpt = None;
+
+    for cause in error.chain() {
+        let message = match (cause.downcast_ref::<ParseError>(), input) {
+            (Some(parse_error), Some(input)) => {
+                let parse_error = parse_error.clone().located(input);
+                excerpt = excerpt.or_else(|| parse_error.excerpt(input));
+                parse_error.to_string()
+            }
+            _ => cause.to_string(),
+        };
+
+        if lines.is_empty() {
+            lines.push(message);
+        } else {
+            lines.push(format!("  caused by: {message}"));
+        }
+    }
+
+    lines.extend(excerpt);
+    lines.join("\n")
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
+/// Extract the message of a panic, which is either a `&str` or a `String` for panics raised by `panic!`.
+pub(crate) fn panic_message(payload: &(dyn Any + Send)) -> String {
+    payload
+        .downcast_ref::<&str>()
+        .map(|message| (*message).to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "unknown panic payload".into())
+}
 
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+fn bench<I: Clone, T>(
+    func: &impl Fn(I) -> T,
+    input: I,
+    format: OutputFormat,
+    base_time: &Duration,
+) -> (Duration, u128, Option<BenchStats>) {
+    if format == OutputFormat::Text {
+        print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+        let _ = stdout().flush();
+    }
 
-    let bench_iterations = cmp::min(
-        10000,
-        cmp::max(
-            Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10),
-            10,
-        ),
-    );
+    let timers = sample(func, input, base_time);
+    let stats = BenchStats::from_samples(&timers);
+
+    (
+        stats.map_or(*base_time, |stats| stats.mean),
+        timers.len() as u128,
+        stats,
+    )
+}
+
+/// Time repeated executions of a function, after warming up. The number of samples is chosen so that sampling
+/// takes roughly one second, based on the duration of a first execution, but there are between 10 and 10,000.
+/// Messages of [`dbg_log!`](crate::dbg_log) are suppressed, as they would be repeated on every execution.
+pub(crate) fn sample<I: Clone, T>(
+    func: &impl Fn(I) -> T,
+    input: I,
+    base_time: &Duration,
+) -> Vec<Duration> {
+    log::suppressed(|| sample_unlogged(func, input, base_time))
+}
+
+fn sample_unlogged<I: Clone, T>(
+    func: &impl Fn(I) -> T,
+    input: I,
+    base_time: &Duration,
+) -> Vec<Duration> {
+    let bench_iterations =
+        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+
+    // warm up caches and branch predictors before taking measurements.
+    for _ in 0..cmp::max(bench_iterations / 10, 1) {
+        black_box(func(black_box(input.clone())));
+    }
 
     let mut timers: Vec<Duration> = vec![];
 
@@ -65,23 +711,12 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
         // need a clone here to make the borrow checker happy.
         let cloned = input.clone();
         let timer = Instant::now();
-        func(cloned);
+        // `black_box` keeps the optimizer from discarding the work or hoisting it out of the loop.
+        black_box(func(black_box(cloned)));
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
-}
-
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+    timers
 }
 
 fn format_duration(duration: &Duration, samples: u128) -> String {
@@ -92,13 +727,37 @@ fn format_duration(duration: &Duration, samples: u128) -> String {
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+fn format_stats(stats: &BenchStats) -> String {
+    let outliers = match stats.outliers {
+        0 => String::new(),
+        1 => " · 1 outlier rejected".into(),
+        n => format!(" · {n} outliers rejected"),
+    };
+
+    format!(
+        "  {ANSI_ITALIC}median {:.1?} · σ {:.1?} · min {:.1?} · p95 {:.1?} · p99 {:.1?} · max {:.1?}{outliers}{ANSI_RESET}",
+        stats.median, stats.std_dev, stats.min, stats.p95, stats.p99, stats.max
+    )
+}
+
+/// Read the accepted answer of a part from `data/answers`, see [`answers`].
+fn read_expected_answer(day: Day, part: u8) -> Option<String> {
+    match answers::read(day) {
+        Ok(answers) => answers.get(part).map(Into::into),
+        Err(e) => {
+            eprintln!("{e}");
+            None
+        }
+    }
+}
+
+fn print_result<T: Display>(result: &Option<T>, part: &str, check: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
     match result {
         Some(result) => {
             if result.to_string().contains('\n') {
-                let str = format!("{part}: ▼ {duration_str}");
+                let str = format!("{part}: ▼{check} {duration_str}");
                 if is_intermediate_result {
                     print!("{str}");
                 } else {
@@ -107,7 +766,7 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                     println!("{result}");
                 }
             } else {
-                let str = format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}");
+                let str = format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{check}{duration_str}");
                 if is_intermediate_result {
                     print!("{str}");
                 } else {
@@ -127,14 +786,14 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
     }
 }
 
-/// Parse the arguments passed to `solve` and try to submit one part of the solution if:
-///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
+/// Parse the arguments passed to `solve` and try to submit one part of the solution if `--submit <part>` names it.
+///
+/// Accepted answers are stored in `data/answers`.
 fn submit_result<T: Display>(
     result: T,
     day: Day,
     part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+) -> Option<Result<Submission, Box<dyn Error>>> {
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
@@ -157,11 +816,156 @@ fn submit_result<T: Display>(
         return None;
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+    let answer = result.to_string();
+
+    // answers that are known to be wrong, and submissions during a cooldown, never reach the website.
+    let records = submissions::read(day).unwrap_or_else(|e| {
+        eprintln!("failed to read submission log: {e}");
+        vec![]
+    });
+    if let Err(refusal) = submissions::check(&records, part, &answer, timestamp()) {
+        return Some(Err(format!("refusing to submit {answer}: {refusal}").into()));
+    }
+
+    let submission = submit_answer(day, part, &answer);
+
+    if let Ok(submission) = &submission {
+        log_submission(day, part, &answer, submission);
+    }
+
+    if matches!(&submission, Ok(submission) if submission.is_correct()) {
+        match answers::save(day, part, &answer) {
+            Ok(()) => println!("🎄 Saved answer to \"{}\".", answers::get_path(day)),
+            Err(e) => eprintln!("failed to save answer: {e}"),
+        }
+    }
+
+    Some(submission)
+}
+
+/// Append a submission to the log of its day and show how long the website asked to wait, if at all.
+fn log_submission(day: Day, part: u8, answer: &str, submission: &Submission) {
+    let now = timestamp();
+    let Some(record) = submissions::Record::from_response(part, answer, &submission.message, now)
+    else {
+        return;
+    };
+
+    if record.verdict != Verdict::Correct {
+        println!("Verdict: {}", record.verdict);
+    }
+    if let Some(retry_after) = record.retry_after {
+        println!(
+            "⏳ The next answer can be submitted in {}.",
+            submissions::format_wait(retry_after.saturating_sub(now))
+        );
+    }
+
+    if let Err(e) = submissions::append(day, &record) {
+        eprintln!("failed to write submission log: {e}");
+    }
+}
+
+/// Submit an answer through the [`aoc_client`], or through aoc-cli if `AOC_CLIENT` asks for it.
+/// Prints the verdict of the website.
+fn submit_answer(day: Day, part: u8, answer: &str) -> Result<Submission, Box<dyn Error>> {
+    if aoc_client::uses_aoc_cli() {
+        if let Err(e) = aoc_cli::check() {
+            eprintln!("{e}");
+            ExitCode::SubmissionFailed.exit();
+        }
+
+        println!("Submitting result via aoc-cli...");
+        let output = aoc_cli::submit(day, part, answer)?;
+        return Ok(Submission {
+            message: String::from_utf8_lossy(&output.stdout).into(),
+        });
+    }
+
+    println!("Submitting result...");
+    let submission = AocClient::from_env()?.submit(day, part, answer)?;
+    println!("{}", submission.message);
+    Ok(submission)
+}
+
+/// (De)serializes a [`Duration`] as an integer amount of nanoseconds.
+pub(crate) mod duration_nanos {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use std::time::Duration;
+
+    pub fn serialize<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u128(duration.as_nanos())
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        let nanos = u128::deserialize(deserializer)?;
+        #[allow(clippy::cast_possible_truncation)]
+        Ok(Duration::from_nanos(nanos as u64))
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_error, run_isolated, Failure, IntoAnswer, ParseError};
+    use anyhow::Context;
+    use std::sync::Arc;
+    use std::thread;
+    use std::time::Duration;
+
+    #[test]
+    fn runs_part() {
+        let func = Arc::new(|x: u32| x * 2);
+        let run = run_isolated(&func, 21, "Part 1", None).unwrap();
+        assert_eq!(run.result, 42);
+    }
+
+    #[test]
+    fn catches_panics() {
+        let func = Arc::new(|x: u32| -> u32 { panic!("no solution for {x}") });
+        let failure = run_isolated(&func, 21, "Part 1", None).unwrap_err();
+        assert_eq!(failure, Failure::Panicked("no solution for 21".into()));
+        assert_eq!(failure.to_string(), "panicked: no solution for 21");
+    }
+
+    #[test]
+    fn times_out() {
+        let func = Arc::new(|()| thread::sleep(Duration::from_secs(5)));
+        let timeout = Duration::from_millis(10);
+        let failure = run_isolated(&func, (), "Part 1", Some(timeout)).unwrap_err();
+        assert_eq!(failure, Failure::TimedOut(timeout));
+        assert_eq!(failure.to_string(), "timed out after 10.0ms");
+    }
+
+    #[test]
+    fn converts_answers() {
+        assert_eq!(Some(42).into_answer().unwrap(), Some(42));
+        assert_eq!(None::<u32>.into_answer().unwrap(), None);
+        assert_eq!(
+            Ok::<_, std::fmt::Error>(42).into_answer().unwrap(),
+            Some(42)
+        );
+        assert!(Err::<u32, _>(std::fmt::Error).into_answer().is_err());
+    }
+
+    #[test]
+    fn formats_error_chain() {
+        let error = "x"
+            .parse::<u32>()
+            .context("line 3 is not a number")
+            .unwrap_err();
+        assert_eq!(
+            format_error(&error, None),
+            "line 3 is not a number\n  caused by: invalid digit found in string"
+        );
+    }
+
+    #[test]
+    fn formats_parse_error_with_excerpt() {
+        let input = "1 2\n3 x\n";
+        let error = anyhow::Error::new(ParseError::new(&input[6..7], "a number"));
+        assert_eq!(
+            format_error(&error, Some(input)),
+            "line 2, column 3: expected a number, found \"x\"\n  |\n2 | 3 x\n  |   ^"
+        );
+    }
 }
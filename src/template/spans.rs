@@ -0,0 +1,365 @@
+/// Module that times named sections of a solution, see [`span!`](crate::span).
+/// With the `spans` feature enabled, the first run of every part records a tree of the spans it entered, which the
+/// runner prints below the result of the part. Without it, spans are empty guards and compile down to nothing.
+use std::{borrow::Cow, fmt::Display, time::Duration};
+
+use serde::{Deserialize, Serialize};
+
+use crate::template::{runner::duration_nanos, ANSI_ITALIC, ANSI_RESET};
+
+/// Times the rest of the enclosing block, or an expression, as a named span.
+///
+/// ```ignore
+/// fn parse(input: &str) -> Network {
+///     advent_of_code::span!("parse");
+///     let regex = advent_of_code::span!("regex", Regex::new(NODE_PATTERN).unwrap());
+///     // ...
+/// }
+/// ```
+///
+/// Spans that are entered while another one is active are nested below it. Spans are only recorded on the thread
+/// that runs a part, e.g. work that is spread over a thread pool is attributed to the span that waits for it.
+#[macro_export]
+macro_rules! span {
+    ($name:expr) => {
+        let _span = $crate::template::spans::enter($name);
+    };
+    ($name:expr, $body:expr) => {{
+        let _span = $crate::template::spans::enter($name);
+        $body
+    }};
+}
+
+/// A span in the tree recorded while a part ran. Spans of the same name below the same parent are merged.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SpanNode {
+    /// The name passed to [`span!`](crate::span), or an owned copy of it if the span was read from a JSON record.
+    pub name: Cow<'static, str>,
+    /// The time spent in the span, summed over all calls.
+    #[serde(rename = "total_nanos", with = "duration_nanos")]
+    pub total: Duration,
+    pub calls: u64,
+    pub children: Vec<SpanNode>,
+}
+
+impl SpanNode {
+    /// The time spent in the span itself, outside of its children.
+    #[must_use]
+    pub fn self_time(&self) -> Duration {
+        let children: Duration = self.children.iter().map(|child| child.total).sum();
+        self.total.saturating_sub(children)
+    }
+}
+
+/// The spans recorded while a part ran, in the order they were first entered.
+/// They are part of the [`PartResult`](crate::template::runner::PartResult) of the part, so that `all` can print the
+/// spans that solution binaries report in their JSON records.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SpanTree {
+    pub roots: Vec<SpanNode>,
+}
+
+impl SpanTree {
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.roots.is_empty()
+    }
+}
+
+impl Display for SpanTree {
+    /// One line per span, e.g. `├─ regex: 11.3ms total · 11.3ms self · 700 calls`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        fn write_nodes(
+            f: &mut std::fmt::Formatter<'_>,
+            nodes: &[SpanNode],
+            indent: &str,
+        ) -> std::fmt::Result {
+            for (i, node) in nodes.iter().enumerate() {
+                let is_last = i == nodes.len() - 1;
+                let (branch, continuation) = if is_last {
+                    ("└─ ", "   ")
+                } else {
+                    ("├─ ", "│  ")
+                };
+                let calls = match node.calls {
+                    1 => "1 call".into(),
+                    calls => format!("{calls} calls"),
+                };
+
+                writeln!(
+                    f,
+                    "  {indent}{branch}{ANSI_ITALIC}{}: {:.1?} total · {:.1?} self · {calls}{ANSI_RESET}",
+                    node.name,
+                    node.total,
+                    node.self_time()
+                )?;
+                write_nodes(f, &node.children, &format!("{indent}{continuation}"))?;
+            }
+
+            Ok(())
+        }
+
+        write_nodes(f, &self.roots, "")
+    }
+}
+
+/// Guard that ends a span when it is dropped. Returned by [`enter`].
+#[must_use = "a span ends as soon as its guard is dropped"]
+pub struct Span {
+    #[cfg(feature = "spans")]
+    is_recorded: bool,
+}
+
+#[cfg(feature = "spans")]
+impl Drop for Span {
+    fn drop(&mut self) {
+        if self.is_recorded {
+            collector::exit();
+        }
+    }
+}
+
+/// Start a span that ends when the returned guard is dropped. Used by the [`span!`](crate::span) macro.
+#[inline(always)]
+pub fn enter(name: &'static str) -> Span {
+    #[cfg(feature = "spans")]
+    {
+        Span {
+            is_recorded: collector::enter(name),
+        }
+    }
+
+    #[cfg(not(feature = "spans"))]
+    {
+        let _ = name;
+        Span {}
+    }
+}
+
+/// Call `func`, recording the spans it enters on the current thread.
+/// Returns no spans if the `spans` feature is disabled.
+pub fn collect<T>(func: impl FnOnce() -> T) -> (T, Option<SpanTree>) {
+    #[cfg(feature = "spans")]
+    {
+        collector::start();
+        let result = func();
+        (result, Some(collector::finish()))
+    }
+
+    #[cfg(not(feature = "spans"))]
+    {
+        (func(), None)
+    }
+}
+
+#[cfg(feature = "spans")]
+mod collector {
+    use super::{SpanNode, SpanTree};
+    use std::{cell::RefCell, time::Instant};
+
+    struct Entry {
+        node: SpanNode,
+        children: Vec<usize>,
+    }
+
+    /// The spans of the current run, stored flat. `roots` and `children` hold indices into `entries`.
+    #[derive(Default)]
+    struct Collector {
+        entries: Vec<Entry>,
+        roots: Vec<usize>,
+        /// The spans that are currently entered, innermost last.
+        stack: Vec<(usize, Instant)>,
+    }
+
+    thread_local! {
+        /// Only set while [`super::collect`] runs, so that benchmark iterations do not record anything.
+        static COLLECTOR: RefCell<Option<Collector>> = const { RefCell::new(None) };
+    }
+
+    pub fn start() {
+        COLLECTOR.with(|collector| *collector.borrow_mut() = Some(Collector::default()));
+    }
+
+    /// Enter the span `name` below the innermost active span. Returns whether the span is recorded.
+    pub fn enter(name: &'static str) -> bool {
+        COLLECTOR.with(|collector| {
+            let mut collector = collector.borrow_mut();
+            let Some(collector) = collector.as_mut() else {
+                return false;
+            };
+
+            let siblings = match collector.stack.last() {
+                Some(&(parent, _)) => &collector.entries[parent].children,
+                None => &collector.roots,
+            };
+
+            let index = match siblings
+                .iter()
+                .find(|&&index| collector.entries[index].node.name == name)
+            {
+                Some(&index) => index,
+                None => {
+                    let index = collector.entries.len();
+                    collector.entries.push(Entry {
+                        node: SpanNode {
+                            name: name.into(),
+                            total: std::time::Duration::ZERO,
+                            calls: 0,
+                            children: vec![],
+                        },
+                        children: vec![],
+                    });
+
+                    match collector.stack.last() {
+                        Some(&(parent, _)) => collector.entries[parent].children.push(index),
+                        None => collector.roots.push(index),
+                    }
+                    index
+                }
+            };
+
+            collector.stack.push((index, Instant::now()));
+            true
+        })
+    }
+
+    pub fn exit() {
+        COLLECTOR.with(|collector| {
+            if let Some(collector) = collector.borrow_mut().as_mut() {
+                if let Some((index, start)) = collector.stack.pop() {
+                    let node = &mut collector.entries[index].node;
+                    node.total += start.elapsed();
+                    node.calls += 1;
+                }
+            }
+        });
+    }
+
+    /// Stop recording and return the tree of spans that were entered since [`start`].
+    pub fn finish() -> SpanTree {
+        fn build(entries: &[Entry], index: usize) -> SpanNode {
+            let entry = &entries[index];
+            SpanNode {
+                children: entry
+                    .children
+                    .iter()
+                    .map(|&child| build(entries, child))
+                    .collect(),
+                ..entry.node.clone()
+            }
+        }
+
+        let collector = COLLECTOR
+            .with(|collector| collector.borrow_mut().take())
+            .unwrap_or_default();
+
+        SpanTree {
+            roots: collector
+                .roots
+                .iter()
+                .map(|&root| build(&collector.entries, root))
+                .collect(),
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{collect, SpanNode, SpanTree};
+    use std::time::Duration;
+
+    fn node(
+        name: &'static str,
+        total_micros: u64,
+        calls: u64,
+        children: Vec<SpanNode>,
+    ) -> SpanNode {
+        SpanNode {
+            name: name.into(),
+            total: Duration::from_micros(total_micros),
+            calls,
+            children,
+        }
+    }
+
+    #[test]
+    fn computes_self_time() {
+        let parse = node("parse", 100, 1, vec![node("regex", 60, 3, vec![])]);
+        assert_eq!(parse.self_time(), Duration::from_micros(40));
+    }
+
+    #[test]
+    fn formats_tree() {
+        let tree = SpanTree {
+            roots: vec![
+                node(
+                    "parse",
+                    100,
+                    1,
+                    vec![node("regex", 60, 3, vec![]), node("nodes", 30, 1, vec![])],
+                ),
+                node("walk", 20, 1, vec![]),
+            ],
+        };
+
+        let lines: Vec<String> = tree
+            .to_string()
+            .lines()
+            .map(|line| line.replace("\x1b[3m", "").replace("\x1b[0m", ""))
+            .collect();
+
+        assert_eq!(
+            lines,
+            [
+                "  ├─ parse: 100.0µs total · 10.0µs self · 1 call",
+                "  │  ├─ regex: 60.0µs total · 60.0µs self · 3 calls",
+                "  │  └─ nodes: 30.0µs total · 30.0µs self · 1 call",
+                "  └─ walk: 20.0µs total · 20.0µs self · 1 call",
+            ]
+        );
+    }
+
+    #[test]
+    fn serializes_tree() {
+        let tree = SpanTree {
+            roots: vec![node("parse", 100, 1, vec![node("regex", 60, 3, vec![])])],
+        };
+
+        let json = serde_json::to_string(&tree).unwrap();
+        assert_eq!(
+            json,
+            r#"{"roots":[{"name":"parse","total_nanos":100000,"calls":1,"children":[{"name":"regex","total_nanos":60000,"calls":3,"children":[]}]}]}"#
+        );
+        assert_eq!(serde_json::from_str::<SpanTree>(&json).unwrap(), tree);
+    }
+
+    #[test]
+    fn collects_nested_spans() {
+        let (result, tree) = collect(|| {
+            crate::span!("outer");
+            for _ in 0..3 {
+                crate::span!("inner", ());
+            }
+            42
+        });
+        assert_eq!(result, 42);
+
+        if cfg!(feature = "spans") {
+            let tree = tree.unwrap();
+            assert_eq!(tree.roots.len(), 1);
+            assert_eq!(tree.roots[0].name, "outer");
+            assert_eq!(tree.roots[0].calls, 1);
+            assert_eq!(tree.roots[0].children[0].name, "inner");
+            assert_eq!(tree.roots[0].children[0].calls, 3);
+        } else {
+            assert!(tree.is_none());
+        }
+
+        // spans outside of `collect` are not recorded.
+        {
+            crate::span!("ignored");
+        }
+        let (_, tree) = collect(|| ());
+        assert!(tree.map_or(true, |tree| tree.is_empty()));
+    }
+}
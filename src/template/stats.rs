@@ -0,0 +1,223 @@
+/// Summary statistics over the samples collected by `runner::bench`.
+use crate::template::runner::duration_nanos;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+/// Statistics of a benchmarked part. All figures are computed after outliers have been rejected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BenchStats {
+    #[serde(rename = "mean_nanos", with = "duration_nanos")]
+    pub mean: Duration,
+    #[serde(rename = "median_nanos", with = "duration_nanos")]
+    pub median: Duration,
+    #[serde(rename = "std_dev_nanos", with = "duration_nanos")]
+    pub std_dev: Duration,
+    #[serde(rename = "min_nanos", with = "duration_nanos")]
+    pub min: Duration,
+    #[serde(rename = "max_nanos", with = "duration_nanos")]
+    pub max: Duration,
+    #[serde(rename = "p95_nanos", with = "duration_nanos")]
+    pub p95: Duration,
+    #[serde(rename = "p99_nanos", with = "duration_nanos")]
+    pub p99: Duration,
+    /// Number of samples that were rejected as outliers.
+    pub outliers: usize,
+}
+
+impl BenchStats {
+    /// Compute statistics from raw samples. Samples outside of Tukey's fences, i.e. more than 1.5 times the
+    /// interquartile range below the first or above the third quartile, are rejected as outliers.
+    /// Returns `None` if there are no samples.
+    #[must_use]
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        let mut all: Vec<f64> = samples.iter().map(|x| x.as_nanos() as f64).collect();
+        all.sort_by(f64::total_cmp);
+
+        let q1 = percentile(&all, 25.0);
+        let q3 = percentile(&all, 75.0);
+        let iqr = q3 - q1;
+        let (lower, upper) = (q1 - 1.5 * iqr, q3 + 1.5 * iqr);
+
+        let kept: Vec<f64> = all
+            .iter()
+            .copied()
+            .filter(|x| (lower..=upper).contains(x))
+            .collect();
+
+        #[allow(clippy::cast_precision_loss)]
+        let len = kept.len() as f64;
+        let mean = kept.iter().sum::<f64>() / len;
+
+        let variance = if kept.len() > 1 {
+            kept.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (len - 1.0)
+        } else {
+            0.0
+        };
+
+        Some(Self {
+            mean: from_nanos(mean),
+            median: from_nanos(percentile(&kept, 50.0)),
+            std_dev: from_nanos(variance.sqrt()),
+            min: from_nanos(kept[0]),
+            max: from_nanos(kept[kept.len() - 1]),
+            p95: from_nanos(percentile(&kept, 95.0)),
+            p99: from_nanos(percentile(&kept, 99.0)),
+            outliers: all.len() - kept.len(),
+        })
+    }
+}
+
+/// Whether two benchmarks differ by more than their noise, decided by Welch's t-test at a 95% confidence level.
+/// `samples` are the numbers of samples that the statistics were computed from, including outliers.
+#[must_use]
+pub fn is_significant(
+    old: &BenchStats,
+    old_samples: u128,
+    new: &BenchStats,
+    new_samples: u128,
+) -> bool {
+    #[allow(clippy::cast_precision_loss)]
+    let kept = |stats: &BenchStats, samples: u128| {
+        (samples.saturating_sub(stats.outliers as u128)).max(1) as f64
+    };
+    let (n1, n2) = (kept(old, old_samples), kept(new, new_samples));
+
+    // squared standard errors of the means.
+    let e1 = old.std_dev.as_secs_f64().powi(2) / n1;
+    let e2 = new.std_dev.as_secs_f64().powi(2) / n2;
+    let difference = (new.mean.as_secs_f64() - old.mean.as_secs_f64()).abs();
+
+    if n1 < 2.0 || n2 < 2.0 {
+        return false;
+    }
+
+    if e1 + e2 == 0.0 {
+        return difference > 0.0;
+    }
+
+    let t = difference / (e1 + e2).sqrt();
+    // Welch–Satterthwaite approximation of the degrees of freedom.
+    let df = (e1 + e2).powi(2) / (e1.powi(2) / (n1 - 1.0) + e2.powi(2) / (n2 - 1.0));
+
+    t > t_critical(df)
+}
+
+/// Two-sided critical value of Student's t-distribution at a 95% confidence level.
+fn t_critical(df: f64) -> f64 {
+    const TABLE: [f64; 30] = [
+        12.706, 4.303, 3.182, 2.776, 2.571, 2.447, 2.365, 2.306, 2.262, 2.228, 2.201, 2.179, 2.160,
+        2.145, 2.131, 2.120, 2.110, 2.101, 2.093, 2.086, 2.080, 2.074, 2.069, 2.064, 2.060, 2.056,
+        2.052, 2.048, 2.045, 2.042,
+    ];
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let df = df.floor().max(1.0) as usize;
+
+    // the normal approximation is good enough beyond the table.
+    TABLE.get(df - 1).copied().unwrap_or(1.960)
+}
+
+fn from_nanos(nanos: f64) -> Duration {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    Duration::from_nanos(nanos.round() as u64)
+}
+
+/// Percentile of sorted values, linearly interpolated between the closest ranks.
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    #[allow(clippy::cast_precision_loss)]
+    let rank = p / 100.0 * (sorted.len() - 1) as f64;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let (lower, upper) = (rank.floor() as usize, rank.ceil() as usize);
+
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (rank - rank.floor())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{is_significant, percentile, BenchStats};
+    use std::time::Duration;
+
+    fn nanos(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|x| Duration::from_nanos(*x)).collect()
+    }
+
+    #[test]
+    fn no_samples() {
+        assert!(BenchStats::from_samples(&[]).is_none());
+    }
+
+    #[test]
+    fn single_sample() {
+        let stats = BenchStats::from_samples(&nanos(&[100])).unwrap();
+        assert_eq!(stats.mean, Duration::from_nanos(100));
+        assert_eq!(stats.median, Duration::from_nanos(100));
+        assert_eq!(stats.std_dev, Duration::ZERO);
+        assert_eq!(stats.outliers, 0);
+    }
+
+    #[test]
+    fn interpolates_percentiles() {
+        let values = [1.0, 2.0, 3.0, 4.0, 5.0];
+        assert_eq!(percentile(&values, 0.0), 1.0);
+        assert_eq!(percentile(&values, 50.0), 3.0);
+        assert_eq!(percentile(&values, 62.5), 3.5);
+        assert_eq!(percentile(&values, 100.0), 5.0);
+    }
+
+    #[test]
+    fn rejects_outliers() {
+        let stats =
+            BenchStats::from_samples(&nanos(&[100, 102, 98, 101, 99, 100, 10_000])).unwrap();
+        assert_eq!(stats.outliers, 1);
+        assert_eq!(stats.mean, Duration::from_nanos(100));
+        assert_eq!(stats.median, Duration::from_nanos(100));
+        assert_eq!(stats.min, Duration::from_nanos(98));
+        assert_eq!(stats.max, Duration::from_nanos(102));
+    }
+
+    #[test]
+    fn computes_sample_standard_deviation() {
+        let stats = BenchStats::from_samples(&nanos(&[1, 2, 3, 4, 5])).unwrap();
+        assert_eq!(stats.outliers, 0);
+        assert_eq!(stats.mean, Duration::from_nanos(3));
+        // sqrt(2.5), rounded to the nearest nanosecond.
+        assert_eq!(stats.std_dev, Duration::from_nanos(2));
+    }
+
+    fn stats(mean: u64, std_dev: u64) -> BenchStats {
+        let [mean, std_dev] = [mean, std_dev].map(Duration::from_nanos);
+        BenchStats {
+            mean,
+            median: mean,
+            std_dev,
+            min: mean,
+            max: mean,
+            p95: mean,
+            p99: mean,
+            outliers: 0,
+        }
+    }
+
+    #[test]
+    fn detects_significant_changes() {
+        // far apart compared with their noise.
+        assert!(is_significant(&stats(1000, 50), 100, &stats(800, 50), 100));
+        // the same difference within the noise of a handful of samples.
+        assert!(!is_significant(&stats(1000, 400), 5, &stats(800, 400), 5));
+        assert!(!is_significant(
+            &stats(1000, 50),
+            100,
+            &stats(1000, 50),
+            100
+        ));
+        // parts that were not benchmarked have no distribution to compare.
+        assert!(!is_significant(&stats(1000, 50), 1, &stats(800, 50), 1));
+        assert!(is_significant(&stats(1000, 0), 10, &stats(800, 0), 10));
+    }
+}
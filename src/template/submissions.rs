@@ -0,0 +1,377 @@
+/// Module that keeps a log of submitted answers in `data/submissions/DD.jsonl`, one JSON record per submission.
+/// Before an answer is submitted, the log is checked so that known-wrong answers, answers outside of the bounds
+/// given by earlier "too high" and "too low" responses, and submissions during a cooldown are refused locally.
+use std::{
+    fmt::{self, Display},
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::Path,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::Day;
+
+/// How the website responded to a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Verdict {
+    Correct,
+    Wrong,
+    TooHigh,
+    TooLow,
+    /// The answer was not checked, as an earlier answer was submitted too recently.
+    RateLimited,
+    /// The answer was not checked, as the part is already solved.
+    AlreadySolved,
+}
+
+impl Verdict {
+    /// Classify the message of a response, as returned by the website or printed by aoc-cli.
+    #[must_use]
+    pub fn classify(message: &str) -> Option<Self> {
+        let message = message.to_lowercase();
+
+        if message.contains("that's the right answer") {
+            Some(Verdict::Correct)
+        } else if message.contains("that's not the right answer") {
+            if message.contains("too high") {
+                Some(Verdict::TooHigh)
+            } else if message.contains("too low") {
+                Some(Verdict::TooLow)
+            } else {
+                Some(Verdict::Wrong)
+            }
+        } else if message.contains("you gave an answer too recently") {
+            Some(Verdict::RateLimited)
+        } else if message.contains("did you already complete it") {
+            Some(Verdict::AlreadySolved)
+        } else {
+            None
+        }
+    }
+
+    /// Whether the website judged the answer, i.e. whether it tells anything about the answer.
+    #[must_use]
+    pub fn is_judgement(self) -> bool {
+        !matches!(self, Verdict::RateLimited | Verdict::AlreadySolved)
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Verdict::Correct => "correct",
+            Verdict::Wrong => "wrong",
+            Verdict::TooHigh => "too high",
+            Verdict::TooLow => "too low",
+            Verdict::RateLimited => "rate-limited",
+            Verdict::AlreadySolved => "already solved",
+        })
+    }
+}
+
+/// One submitted answer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Record {
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+    /// Seconds since the unix epoch before which the website does not accept another answer.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_after: Option<u64>,
+}
+
+impl Record {
+    /// Create the record of a submission from the message of its response.
+    /// Returns `None` if the message cannot be classified.
+    #[must_use]
+    pub fn from_response(part: u8, answer: &str, message: &str, timestamp: u64) -> Option<Self> {
+        Some(Record {
+            timestamp,
+            part,
+            answer: answer.into(),
+            verdict: Verdict::classify(message)?,
+            retry_after: parse_wait(message).map(|wait| timestamp + wait),
+        })
+    }
+}
+
+/// The reason why an answer is not submitted.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Refusal {
+    /// The part was already solved with an answer.
+    Solved(String),
+    /// The answer was already submitted and judged wrong.
+    KnownWrong(Verdict),
+    /// The answer is not lower than an answer that was too high.
+    TooHigh(String),
+    /// The answer is not higher than an answer that was too low.
+    TooLow(String),
+    /// The website does not accept another answer for this many seconds.
+    Cooldown(u64),
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Refusal::Solved(answer) => write!(f, "the part was already solved with {answer}."),
+            Refusal::KnownWrong(verdict) => {
+                write!(f, "the answer was already submitted and is {verdict}.")
+            }
+            Refusal::TooHigh(bound) => write!(
+                f,
+                "the answer is too high, as {bound} was already too high."
+            ),
+            Refusal::TooLow(bound) => {
+                write!(f, "the answer is too low, as {bound} was already too low.")
+            }
+            Refusal::Cooldown(remaining) => write!(
+                f,
+                "an answer was submitted too recently, wait {} before trying again.",
+                format_wait(*remaining)
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Refusal {}
+
+#[must_use]
+pub fn get_path(day: Day) -> String {
+    format!("data/submissions/{day}.jsonl")
+}
+
+/// Read all submissions of a day, oldest first. Lines that cannot be parsed are skipped.
+pub fn read(day: Day) -> io::Result<Vec<Record>> {
+    match fs::read_to_string(get_path(day)) {
+        Ok(contents) => Ok(contents
+            .lines()
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .collect()),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(vec![]),
+        Err(e) => Err(e),
+    }
+}
+
+/// Append a submission to the log of a day, creating it if necessary.
+pub fn append(day: Day, record: &Record) -> io::Result<()> {
+    let path = get_path(day);
+    if let Some(dir) = Path::new(&path).parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    let mut file = OpenOptions::new().create(true).append(true).open(&path)?;
+    writeln!(file, "{}", serde_json::to_string(record)?)
+}
+
+/// Check whether `answer` should be submitted for `part`, given the earlier submissions of its day.
+/// Bounds only apply to answers that are integers. A cooldown applies to both parts, as the website limits the rate
+/// of submissions per user rather than per part.
+pub fn check(records: &[Record], part: u8, answer: &str, now: u64) -> Result<(), Refusal> {
+    let cooldown = cooldown(records, now);
+    let records: Vec<&Record> = records.iter().filter(|x| x.part == part).collect();
+
+    if let Some(solved) = records.iter().find(|x| x.verdict == Verdict::Correct) {
+        return Err(Refusal::Solved(solved.answer.clone()));
+    }
+
+    if let Some(known) = records
+        .iter()
+        .find(|x| x.answer == answer && x.verdict.is_judgement())
+    {
+        return Err(Refusal::KnownWrong(known.verdict));
+    }
+
+    if let Ok(value) = answer.parse::<i128>() {
+        let bound = |verdict| {
+            records
+                .iter()
+                .filter(move |x| x.verdict == verdict)
+                .filter_map(|x| Some((x.answer.parse::<i128>().ok()?, &x.answer)))
+        };
+
+        if let Some((_, high)) = bound(Verdict::TooHigh).filter(|(x, _)| value >= *x).min() {
+            return Err(Refusal::TooHigh(high.clone()));
+        }
+        if let Some((_, low)) = bound(Verdict::TooLow).filter(|(x, _)| value <= *x).max() {
+            return Err(Refusal::TooLow(low.clone()));
+        }
+    }
+
+    match cooldown {
+        Some(remaining) => Err(Refusal::Cooldown(remaining)),
+        None => Ok(()),
+    }
+}
+
+/// The seconds until the website accepts another answer, if an earlier response asked to wait.
+fn cooldown(records: &[Record], now: u64) -> Option<u64> {
+    records
+        .iter()
+        .filter_map(|x| x.retry_after)
+        .max()
+        .filter(|retry_after| *retry_after > now)
+        .map(|retry_after| retry_after - now)
+}
+
+/// The seconds a response asks to wait before submitting again, as in "You have 1m 36s left to wait." after a
+/// rate-limited submission or "please wait 5 minutes before trying again." after a wrong one.
+fn parse_wait(message: &str) -> Option<u64> {
+    let message = message.to_lowercase();
+
+    if let Some(end) = message.find(" left to wait") {
+        let start = message[..end].rfind("you have ")? + "you have ".len();
+        return message[start..end]
+            .split_whitespace()
+            .map(|token| {
+                let (value, unit) = token.split_at(token.find(|c: char| !c.is_ascii_digit())?);
+                let value: u64 = value.parse().ok()?;
+                match unit {
+                    "h" => Some(value * 3600),
+                    "m" => Some(value * 60),
+                    "s" => Some(value),
+                    _ => None,
+                }
+            })
+            .sum();
+    }
+
+    let start = message.find("wait ")? + "wait ".len();
+    let mut words = message[start..].split_whitespace();
+    let value = match words.next()? {
+        "a" | "an" | "one" => 1,
+        value => value.parse().ok()?,
+    };
+    match words.next()?.trim_end_matches(['.', ',']) {
+        "minute" | "minutes" => Some(value * 60),
+        "second" | "seconds" => Some(value),
+        _ => None,
+    }
+}
+
+/// Format seconds to wait like `1m 36s`.
+#[must_use]
+pub fn format_wait(seconds: u64) -> String {
+    match (seconds / 60, seconds % 60) {
+        (0, seconds) => format!("{seconds}s"),
+        (minutes, 0) => format!("{minutes}m"),
+        (minutes, seconds) => format!("{minutes}m {seconds}s"),
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{check, format_wait, parse_wait, Record, Refusal, Verdict};
+
+    const TOO_HIGH: &str = "That's not the right answer; your answer is too high.  If you're stuck, make sure \
+        you're using the full input data. Please wait one minute before trying again. [[Return to Day 1]](/2023/day/1)";
+    const RATE_LIMITED: &str =
+        "You gave an answer too recently; you have to wait after submitting an answer \
+        before trying again.  You have 1m 36s left to wait. [[Return to Day 1]](/2023/day/1)";
+
+    fn record(answer: &str, verdict: Verdict, retry_after: Option<u64>) -> Record {
+        Record {
+            timestamp: 0,
+            part: 1,
+            answer: answer.into(),
+            verdict,
+            retry_after,
+        }
+    }
+
+    #[test]
+    fn classifies_responses() {
+        let classify = Verdict::classify;
+        assert_eq!(
+            classify("That's the right answer!  You are one gold star closer."),
+            Some(Verdict::Correct)
+        );
+        assert_eq!(classify(TOO_HIGH), Some(Verdict::TooHigh));
+        assert_eq!(
+            classify("That's not the right answer; your answer is too low."),
+            Some(Verdict::TooLow)
+        );
+        assert_eq!(
+            classify("That's not the right answer.  If you're stuck, ..."),
+            Some(Verdict::Wrong)
+        );
+        assert_eq!(classify(RATE_LIMITED), Some(Verdict::RateLimited));
+        assert_eq!(
+            classify("You don't seem to be solving the right level.  Did you already complete it?"),
+            Some(Verdict::AlreadySolved)
+        );
+        assert_eq!(classify("500 Internal Server Error"), None);
+    }
+
+    #[test]
+    fn parses_waits() {
+        assert_eq!(parse_wait(TOO_HIGH), Some(60));
+        assert_eq!(parse_wait(RATE_LIMITED), Some(96));
+        assert_eq!(
+            parse_wait("Because you have guessed incorrectly 4 times on this puzzle, please wait 5 minutes before trying again."),
+            Some(300)
+        );
+        assert_eq!(parse_wait("You have 36s left to wait."), Some(36));
+        assert_eq!(parse_wait("That's the right answer!"), None);
+
+        let parsed = Record::from_response(1, "42", TOO_HIGH, 1000).unwrap();
+        assert_eq!(
+            parsed,
+            Record {
+                timestamp: 1000,
+                ..record("42", Verdict::TooHigh, Some(1060))
+            }
+        );
+    }
+
+    #[test]
+    fn refuses_known_answers() {
+        let records = vec![
+            record("10", Verdict::TooLow, None),
+            record("50", Verdict::TooHigh, None),
+            record("30", Verdict::Wrong, Some(100)),
+            record("20", Verdict::RateLimited, Some(160)),
+        ];
+
+        assert_eq!(
+            check(&records, 1, "30", 200),
+            Err(Refusal::KnownWrong(Verdict::Wrong))
+        );
+        assert_eq!(
+            check(&records, 1, "51", 200),
+            Err(Refusal::TooHigh("50".into()))
+        );
+        assert_eq!(
+            check(&records, 1, "70", 200),
+            Err(Refusal::TooHigh("50".into()))
+        );
+        assert_eq!(
+            check(&records, 1, "-3", 200),
+            Err(Refusal::TooLow("10".into()))
+        );
+        assert_eq!(check(&records, 1, "abc", 200), Ok(()));
+        // a rate-limited answer was never checked.
+        assert_eq!(check(&records, 1, "20", 200), Ok(()));
+        assert_eq!(check(&records, 1, "20", 150), Err(Refusal::Cooldown(10)));
+        // answers are only known wrong for their own part, but a cooldown affects both parts.
+        assert_eq!(check(&records, 2, "30", 200), Ok(()));
+        assert_eq!(check(&records, 2, "30", 150), Err(Refusal::Cooldown(10)));
+
+        let mut records = records;
+        records.push(record("25", Verdict::Correct, None));
+        assert_eq!(
+            check(&records, 1, "26", 200),
+            Err(Refusal::Solved("25".into()))
+        );
+    }
+
+    #[test]
+    fn formats_waits() {
+        assert_eq!(format_wait(36), "36s");
+        assert_eq!(format_wait(300), "5m");
+        assert_eq!(format_wait(96), "1m 36s");
+    }
+}
@@ -0,0 +1,596 @@
+/// Module that lets solutions draw frames of a character grid, e.g. to watch a path being traced.
+/// With the `visualize` feature enabled, the frames of the first run of every part are recorded, and the runner
+/// replays them in the terminal (`--visualize`) or exports them to a file (`--export <path>`).
+/// Without it, [`frame`] compiles down to nothing and the closure that builds a frame is never called.
+use std::{
+    env,
+    fmt::Write as _,
+    fs,
+    io::{self, stdout, Write},
+    path::{Path, PathBuf},
+    thread,
+    time::Duration,
+};
+
+use crate::template::{ANSI_BOLD, ANSI_CLEAR, ANSI_ITALIC, ANSI_RESET};
+
+/// Frames beyond this number are dropped, so that a frame per step of a long loop cannot exhaust memory.
+pub const MAX_FRAMES: usize = 10_000;
+
+/// Frames per second that frames are replayed with, unless `--fps` is passed.
+pub const DEFAULT_FPS: f64 = 10.0;
+
+/// Width and height of a cell in exported PPM images, in pixels.
+const PPM_CELL_SIZE: usize = 4;
+
+/// Width and height of a cell in exported SVGs.
+const SVG_CELL_SIZE: usize = 12;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    Gray,
+}
+
+impl Color {
+    fn ansi_fg(self) -> u8 {
+        match self {
+            Color::Red => 31,
+            Color::Green => 32,
+            Color::Yellow => 33,
+            Color::Blue => 34,
+            Color::Magenta => 35,
+            Color::Cyan => 36,
+            Color::Gray => 90,
+        }
+    }
+
+    fn ansi_bg(self) -> u8 {
+        self.ansi_fg() + 10
+    }
+
+    fn rgb(self) -> [u8; 3] {
+        match self {
+            Color::Red => [220, 50, 47],
+            Color::Green => [133, 153, 0],
+            Color::Yellow => [181, 137, 0],
+            Color::Blue => [38, 139, 210],
+            Color::Magenta => [211, 54, 130],
+            Color::Cyan => [42, 161, 152],
+            Color::Gray => [88, 110, 117],
+        }
+    }
+
+    fn hex(self) -> String {
+        let [r, g, b] = self.rgb();
+        format!("#{r:02x}{g:02x}{b:02x}")
+    }
+}
+
+/// A character of a [`Frame`], with an optional text and background color.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cell {
+    pub ch: char,
+    pub fg: Option<Color>,
+    pub bg: Option<Color>,
+}
+
+impl Cell {
+    fn plain(ch: char) -> Self {
+        Self {
+            ch,
+            fg: None,
+            bg: None,
+        }
+    }
+}
+
+/// A character grid with highlighted cells, built by a solution and passed to [`frame`].
+/// Coordinates are `(x, y)`, i.e. column and row. Cells outside of the grid are ignored.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Frame {
+    rows: Vec<Vec<Cell>>,
+    caption: Option<String>,
+}
+
+impl Frame {
+    /// A frame showing the lines of `grid`, e.g. the puzzle input.
+    #[must_use]
+    pub fn new(grid: &str) -> Self {
+        Self {
+            rows: grid
+                .lines()
+                .map(|line| line.chars().map(Cell::plain).collect())
+                .collect(),
+            caption: None,
+        }
+    }
+
+    /// A frame of `width` × `height` cells, with the character of each cell returned by `cell(x, y)`.
+    #[must_use]
+    pub fn from_fn(width: usize, height: usize, cell: impl Fn(usize, usize) -> char) -> Self {
+        Self {
+            rows: (0..height)
+                .map(|y| (0..width).map(|x| Cell::plain(cell(x, y))).collect())
+                .collect(),
+            caption: None,
+        }
+    }
+
+    /// Color the background of a cell.
+    #[must_use]
+    pub fn highlight(mut self, x: usize, y: usize, color: Color) -> Self {
+        if let Some(cell) = self.cell_mut(x, y) {
+            cell.bg = Some(color);
+        }
+        self
+    }
+
+    /// Color the background of every cell in `cells`.
+    #[must_use]
+    pub fn highlight_all(
+        mut self,
+        cells: impl IntoIterator<Item = (usize, usize)>,
+        color: Color,
+    ) -> Self {
+        for (x, y) in cells {
+            self = self.highlight(x, y, color);
+        }
+        self
+    }
+
+    /// Draw `ch` in `color` over a cell, e.g. to mark the current position.
+    #[must_use]
+    pub fn overlay(mut self, x: usize, y: usize, ch: char, color: Color) -> Self {
+        if let Some(cell) = self.cell_mut(x, y) {
+            cell.ch = ch;
+            cell.fg = Some(color);
+        }
+        self
+    }
+
+    /// A line of text that is shown below the grid, e.g. the current step.
+    #[must_use]
+    pub fn caption(mut self, caption: impl Into<String>) -> Self {
+        self.caption = Some(caption.into());
+        self
+    }
+
+    #[must_use]
+    pub fn width(&self) -> usize {
+        self.rows.iter().map(Vec::len).max().unwrap_or(0)
+    }
+
+    #[must_use]
+    pub fn height(&self) -> usize {
+        self.rows.len()
+    }
+
+    #[must_use]
+    pub fn cell(&self, x: usize, y: usize) -> Option<&Cell> {
+        self.rows.get(y)?.get(x)
+    }
+
+    fn cell_mut(&mut self, x: usize, y: usize) -> Option<&mut Cell> {
+        self.rows.get_mut(y)?.get_mut(x)
+    }
+
+    /// The frame with ANSI escape codes for its colors, one line per row, followed by the caption.
+    #[must_use]
+    pub fn to_ansi(&self) -> String {
+        let mut text = String::new();
+
+        for row in &self.rows {
+            for cell in row {
+                let codes: Vec<String> = [cell.fg.map(Color::ansi_fg), cell.bg.map(Color::ansi_bg)]
+                    .into_iter()
+                    .flatten()
+                    .map(|code| code.to_string())
+                    .collect();
+
+                if codes.is_empty() {
+                    text.push(cell.ch);
+                } else {
+                    let _ = write!(text, "\x1b[{}m{}{ANSI_RESET}", codes.join(";"), cell.ch);
+                }
+            }
+            text.push('\n');
+        }
+
+        if let Some(caption) = &self.caption {
+            let _ = writeln!(text, "{ANSI_ITALIC}{caption}{ANSI_RESET}");
+        }
+
+        text
+    }
+
+    /// The frame as a binary PPM image, with a square of [`PPM_CELL_SIZE`] pixels per cell.
+    /// Cells take their background color, or their text color if they only have one. Other cells are white if
+    /// they hold a character other than `.` or a space, and black otherwise.
+    #[must_use]
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let (width, height) = (self.width() * PPM_CELL_SIZE, self.height() * PPM_CELL_SIZE);
+        let mut image = format!("P6\n{width} {height}\n255\n").into_bytes();
+
+        for y in 0..height {
+            for x in 0..width {
+                let cell = self.cell(x / PPM_CELL_SIZE, y / PPM_CELL_SIZE);
+                let color = match cell.and_then(|cell| cell.bg.or(cell.fg)) {
+                    Some(color) => color.rgb(),
+                    None if cell.is_some_and(|cell| !matches!(cell.ch, '.' | ' ')) => {
+                        [238, 238, 238]
+                    }
+                    None => [0, 0, 0],
+                };
+                image.extend(color);
+            }
+        }
+
+        image
+    }
+}
+
+/// Record a frame built by `build`, if the current part is visualized.
+/// `build` is only called while recording, so building a frame costs nothing in benchmarks.
+#[inline(always)]
+pub fn frame(build: impl FnOnce() -> Frame) {
+    #[cfg(feature = "visualize")]
+    recorder::record(build);
+
+    #[cfg(not(feature = "visualize"))]
+    let _ = build;
+}
+
+/// Call `func`, recording the frames it emits on the current thread.
+/// Returns no frames if the `visualize` feature is disabled.
+pub fn collect<T>(func: impl FnOnce() -> T) -> (T, Option<Frames>) {
+    #[cfg(feature = "visualize")]
+    {
+        recorder::start();
+        let result = func();
+        (result, Some(recorder::finish()))
+    }
+
+    #[cfg(not(feature = "visualize"))]
+    {
+        (func(), None)
+    }
+}
+
+/// The frames recorded while a part ran.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Frames {
+    pub frames: Vec<Frame>,
+    /// The number of frames beyond [`MAX_FRAMES`] that were dropped.
+    pub dropped: usize,
+}
+
+#[cfg(feature = "visualize")]
+mod recorder {
+    use super::{Frame, Frames, MAX_FRAMES};
+    use std::cell::RefCell;
+
+    thread_local! {
+        /// Only set while [`super::collect`] runs, so that benchmark iterations do not record anything.
+        static FRAMES: RefCell<Option<Frames>> = const { RefCell::new(None) };
+    }
+
+    pub fn start() {
+        FRAMES.with(|frames| *frames.borrow_mut() = Some(Frames::default()));
+    }
+
+    pub fn record(build: impl FnOnce() -> Frame) {
+        FRAMES.with(|frames| {
+            if let Some(frames) = frames.borrow_mut().as_mut() {
+                if frames.frames.len() < MAX_FRAMES {
+                    frames.frames.push(build());
+                } else {
+                    frames.dropped += 1;
+                }
+            }
+        });
+    }
+
+    pub fn finish() -> Frames {
+        FRAMES
+            .with(|frames| frames.borrow_mut().take())
+            .unwrap_or_default()
+    }
+}
+
+/// How recorded frames are shown, read from the flags passed to the current process.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Options {
+    /// Replay frames in the terminal, see `--visualize`.
+    pub replay: bool,
+    pub fps: f64,
+    /// Export frames to this file, see `--export <path>`.
+    pub export: Option<PathBuf>,
+}
+
+impl Options {
+    #[must_use]
+    pub fn from_args() -> Self {
+        let args: Vec<String> = env::args().collect();
+        let value_of = |flag: &str| {
+            args.iter()
+                .position(|x| x == flag)
+                .and_then(|i| args.get(i + 1))
+        };
+
+        Self {
+            replay: args.iter().any(|x| x == "--visualize"),
+            fps: value_of("--fps")
+                .and_then(|x| x.parse().ok())
+                .filter(|&fps: &f64| fps > 0.0)
+                .unwrap_or(DEFAULT_FPS),
+            export: value_of("--export").map(PathBuf::from),
+        }
+    }
+}
+
+/// Replay and export the frames of a part, as requested by `options`. `label` names the part, e.g. `part1`.
+pub fn show(frames: &Frames, label: &str, options: &Options) {
+    if frames.frames.is_empty() {
+        return;
+    }
+
+    if options.replay {
+        replay(frames, options.fps);
+    }
+
+    if let Some(path) = &options.export {
+        let path = export_path(path, label);
+
+        match export(&frames.frames, &path, options.fps) {
+            Ok(()) => println!(
+                "  {ANSI_ITALIC}Exported {} frame(s) to {}{ANSI_RESET}",
+                frames.frames.len(),
+                path.display()
+            ),
+            Err(e) => eprintln!("  Failed to export frames to {}: {e}", path.display()),
+        }
+    }
+
+    if frames.dropped > 0 {
+        println!(
+            "  {ANSI_ITALIC}Dropped {} frames beyond the first {MAX_FRAMES}.{ANSI_RESET}",
+            frames.dropped
+        );
+    }
+}
+
+/// Draw the frames in place, one after another.
+fn replay(frames: &Frames, fps: f64) {
+    let delay = Duration::from_secs_f64(1.0 / fps);
+    let mut previous_lines = 0;
+
+    for frame in &frames.frames {
+        let text = frame.to_ansi();
+
+        // move back up over the previous frame and clear everything below, so that frames are drawn in place.
+        if previous_lines > 0 {
+            print!("\x1b[{previous_lines}A\x1b[J");
+        }
+        print!("{text}");
+        let _ = stdout().flush();
+
+        previous_lines = text.lines().count();
+        thread::sleep(delay);
+    }
+}
+
+/// The file that the frames of a part are exported to, e.g. `vis-part1.svg` for `vis.svg`.
+#[must_use]
+pub fn export_path(path: &Path, label: &str) -> PathBuf {
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let name = match path.extension() {
+        Some(extension) => format!("{stem}-{label}.{}", extension.to_string_lossy()),
+        None => format!("{stem}-{label}"),
+    };
+    path.with_file_name(name)
+}
+
+/// Export frames in the format given by the extension of `path`:
+///  1. `.svg`: a single animated SVG, which browsers play in a loop.
+///  2. `.ppm`: one image per frame, numbered like `vis-part1-0001.ppm`.
+///  3. anything else: an ANSI log that replays in a terminal, e.g. with `cat`.
+pub fn export(frames: &[Frame], path: &Path, fps: f64) -> io::Result<()> {
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        fs::create_dir_all(dir)?;
+    }
+
+    match path.extension().and_then(|extension| extension.to_str()) {
+        Some("svg") => fs::write(path, to_svg(frames, fps)),
+        Some("ppm") => {
+            for (i, frame) in frames.iter().enumerate() {
+                fs::write(export_path(path, &format!("{:04}", i + 1)), frame.to_ppm())?;
+            }
+            Ok(())
+        }
+        _ => fs::write(path, to_ansi_log(frames)),
+    }
+}
+
+/// Every frame on a cleared screen, under a header with its number.
+#[must_use]
+pub fn to_ansi_log(frames: &[Frame]) -> String {
+    frames
+        .iter()
+        .enumerate()
+        .map(|(i, frame)| {
+            format!(
+                "{ANSI_CLEAR}{ANSI_BOLD}Frame {}/{}{ANSI_RESET}\n{}",
+                i + 1,
+                frames.len(),
+                frame.to_ansi()
+            )
+        })
+        .collect()
+}
+
+/// An SVG that shows one frame after another. Every frame is a group that is only visible during its time slot.
+#[must_use]
+pub fn to_svg(frames: &[Frame], fps: f64) -> String {
+    let columns = frames.iter().map(Frame::width).max().unwrap_or(0);
+    let rows = frames
+        .iter()
+        .map(|frame| frame.height() + usize::from(frame.caption.is_some()))
+        .max()
+        .unwrap_or(0);
+    let (width, height) = (columns * SVG_CELL_SIZE, rows * SVG_CELL_SIZE);
+    #[allow(clippy::cast_precision_loss)]
+    let duration = frames.len() as f64 / fps;
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{width}\" height=\"{height}\" \
+         font-family=\"monospace\" font-size=\"{SVG_CELL_SIZE}\">\n\
+         <rect width=\"100%\" height=\"100%\" fill=\"#002b36\"/>\n"
+    );
+
+    for (i, frame) in frames.iter().enumerate() {
+        #[allow(clippy::cast_precision_loss)]
+        let (start, end) = (
+            i as f64 / frames.len() as f64,
+            (i + 1) as f64 / frames.len() as f64,
+        );
+
+        let _ = writeln!(svg, "<g visibility=\"hidden\">");
+        let _ = writeln!(
+            svg,
+            "<animate attributeName=\"visibility\" values=\"hidden;visible;hidden\" \
+             keyTimes=\"0;{start:.6};{end:.6}\" dur=\"{duration:.3}s\" calcMode=\"discrete\" \
+             repeatCount=\"indefinite\"/>"
+        );
+
+        for (y, row) in frame.rows.iter().enumerate() {
+            for (x, cell) in row.iter().enumerate() {
+                let (px, py) = (x * SVG_CELL_SIZE, y * SVG_CELL_SIZE);
+
+                if let Some(bg) = cell.bg {
+                    let _ = writeln!(
+                        svg,
+                        "<rect x=\"{px}\" y=\"{py}\" width=\"{SVG_CELL_SIZE}\" height=\"{SVG_CELL_SIZE}\" fill=\"{}\"/>",
+                        bg.hex()
+                    );
+                }
+
+                if !cell.ch.is_whitespace() {
+                    let fill = cell.fg.map_or_else(|| "#eee8d5".into(), Color::hex);
+                    let _ = writeln!(
+                        svg,
+                        "<text x=\"{px}\" y=\"{}\" fill=\"{fill}\">{}</text>",
+                        py + SVG_CELL_SIZE - 2,
+                        escape_xml(&cell.ch.to_string())
+                    );
+                }
+            }
+        }
+
+        if let Some(caption) = &frame.caption {
+            let _ = writeln!(
+                svg,
+                "<text x=\"0\" y=\"{}\" fill=\"#93a1a1\">{}</text>",
+                (frame.height() + 1) * SVG_CELL_SIZE - 2,
+                escape_xml(caption)
+            );
+        }
+
+        let _ = writeln!(svg, "</g>");
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{collect, export_path, frame, to_svg, Color, Frame};
+    use std::path::{Path, PathBuf};
+
+    #[test]
+    fn builds_frames() {
+        let frame = Frame::new("..#\n.S.")
+            .highlight(0, 0, Color::Red)
+            .overlay(1, 1, '@', Color::Green)
+            .highlight(5, 5, Color::Blue)
+            .caption("step 1");
+
+        assert_eq!((frame.width(), frame.height()), (3, 2));
+        assert_eq!(frame.cell(0, 0).unwrap().bg, Some(Color::Red));
+        assert_eq!(frame.cell(1, 1).unwrap().ch, '@');
+        assert_eq!(frame.cell(1, 1).unwrap().fg, Some(Color::Green));
+        assert!(frame.cell(5, 5).is_none());
+        assert_eq!(
+            frame.to_ansi(),
+            "\x1b[41m.\x1b[0m.#\n.\x1b[32m@\x1b[0m.\n\x1b[3mstep 1\x1b[0m\n"
+        );
+    }
+
+    #[test]
+    fn renders_ppm() {
+        let image = Frame::from_fn(2, 1, |x, _| if x == 0 { '#' } else { '.' }).to_ppm();
+        let header = b"P6\n8 4\n255\n";
+
+        assert_eq!(&image[..header.len()], header);
+        assert_eq!(image.len(), header.len() + 8 * 4 * 3);
+        assert_eq!(&image[header.len()..header.len() + 3], &[238, 238, 238]);
+        assert_eq!(&image[image.len() - 3..], &[0, 0, 0]);
+    }
+
+    #[test]
+    fn renders_svg() {
+        let frames = [
+            Frame::new("<>"),
+            Frame::new("ab").highlight(0, 0, Color::Cyan),
+        ];
+        let svg = to_svg(&frames, 2.0);
+
+        assert_eq!(svg.matches("<g visibility=\"hidden\">").count(), 2);
+        assert!(svg.contains("keyTimes=\"0;0.500000;1.000000\" dur=\"1.000s\""));
+        assert!(svg.contains("&lt;"));
+        assert!(svg.contains("fill=\"#2aa198\""));
+    }
+
+    #[test]
+    fn names_exported_files() {
+        assert_eq!(
+            export_path(Path::new("out/vis.svg"), "part1"),
+            PathBuf::from("out/vis-part1.svg")
+        );
+        assert_eq!(
+            export_path(Path::new("vis"), "parse"),
+            PathBuf::from("vis-parse")
+        );
+    }
+
+    #[test]
+    fn records_frames() {
+        let (_, frames) = collect(|| {
+            for i in 0..3 {
+                frame(|| Frame::new(".").caption(format!("step {i}")));
+            }
+        });
+
+        if cfg!(feature = "visualize") {
+            assert_eq!(frames.unwrap().frames.len(), 3);
+        } else {
+            assert!(frames.is_none());
+        }
+
+        // frames outside of `collect` are never built.
+        frame(|| unreachable!());
+    }
+}
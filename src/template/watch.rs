@@ -0,0 +1,155 @@
+/// Module that detects changes to the files of a day for `solve --watch`.
+/// Files are polled for their modification times, so no platform-specific file system notifications are needed.
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use crate::template::runner::{PartResult, PARSE_PART};
+use crate::Day;
+
+/// The files of a day that trigger a re-run: its solution, inputs, examples and all library sources.
+#[must_use]
+pub fn watched_paths(day: Day) -> Vec<PathBuf> {
+    vec![
+        PathBuf::from(format!("src/bin/{day}.rs")),
+        PathBuf::from(format!("data/inputs/{day}.txt")),
+        PathBuf::from(format!("data/inputs/{day}")),
+        PathBuf::from(format!("data/examples/{day}.txt")),
+        PathBuf::from(format!("data/examples/{day}-1.txt")),
+        PathBuf::from(format!("data/examples/{day}-2.txt")),
+        PathBuf::from("src"),
+    ]
+}
+
+/// Modification times of all files below a set of paths.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Snapshot(BTreeMap<PathBuf, SystemTime>);
+
+impl Snapshot {
+    /// Record the files below `paths`. Paths that do not exist are skipped, so that creating them counts as a change.
+    /// Solutions of other days in `src/bin` are not part of the snapshot.
+    #[must_use]
+    pub fn take(paths: &[PathBuf]) -> Self {
+        let mut files = BTreeMap::new();
+
+        for path in paths {
+            collect(path, &mut files);
+        }
+
+        Self(files)
+    }
+
+    /// Block until any file below `paths` was modified, created or deleted, checking every `interval`.
+    pub fn wait_for_change(&self, paths: &[PathBuf], interval: Duration) {
+        while Self::take(paths) == *self {
+            thread::sleep(interval);
+        }
+    }
+}
+
+fn collect(path: &Path, files: &mut BTreeMap<PathBuf, SystemTime>) {
+    let Ok(metadata) = fs::metadata(path) else {
+        return;
+    };
+
+    if metadata.is_dir() {
+        if path.ends_with("src/bin") {
+            return;
+        }
+
+        let Ok(entries) = fs::read_dir(path) else {
+            return;
+        };
+
+        for entry in entries.flatten() {
+            collect(&entry.path(), files);
+        }
+    } else if let Ok(modified) = metadata.modified() {
+        files.insert(path.to_path_buf(), modified);
+    }
+}
+
+/// An answer that differs from the previous run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnswerChange {
+    pub part: u8,
+    pub input: Option<String>,
+    pub previous: Option<String>,
+    pub current: Option<String>,
+}
+
+/// Compare the answers of two runs of the same day. Parts are matched by their part number and input.
+#[must_use]
+pub fn answer_changes(previous: &[PartResult], current: &[PartResult]) -> Vec<AnswerChange> {
+    current
+        .iter()
+        .filter(|result| result.part != PARSE_PART)
+        .filter_map(|result| {
+            let before = previous
+                .iter()
+                .find(|p| p.part == result.part && p.input == result.input)
+                .and_then(|p| p.answer.clone());
+
+            (before != result.answer).then(|| AnswerChange {
+                part: result.part,
+                input: result.input.clone(),
+                previous: before,
+                current: result.answer.clone(),
+            })
+        })
+        .collect()
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{answer_changes, AnswerChange, Snapshot};
+    use crate::day;
+    use crate::template::runner::{PartResult, PartStatus};
+    use std::{env, fs};
+
+    fn result(part: u8, answer: Option<&str>) -> PartResult {
+        PartResult {
+            answer: answer.map(Into::into),
+            status: PartStatus::Solved,
+            ..PartResult::new(day!(1), part)
+        }
+    }
+
+    #[test]
+    fn detects_changed_answers() {
+        let previous = [result(1, Some("42")), result(2, None)];
+        let current = [result(0, None), result(1, Some("42")), result(2, Some("7"))];
+
+        assert_eq!(
+            answer_changes(&previous, &current),
+            vec![AnswerChange {
+                part: 2,
+                input: None,
+                previous: None,
+                current: Some("7".into()),
+            }]
+        );
+        assert!(answer_changes(&current, &current).is_empty());
+    }
+
+    #[test]
+    fn detects_created_files() {
+        let dir = env::temp_dir().join(format!("aoc-watch-{}", std::process::id()));
+        let file = dir.join("01.txt");
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+
+        let paths = [dir.clone(), dir.join("missing")];
+        let before = Snapshot::take(&paths);
+        fs::write(&file, "input").unwrap();
+        let after = Snapshot::take(&paths);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_ne!(before, after);
+        assert_eq!(before, Snapshot::take(&paths));
+    }
+}